@@ -1,4 +1,10 @@
+#[cfg(feature = "autolaunch")]
 pub mod os_autolaunch;
+#[cfg(feature = "sysinfo")]
 pub mod os_sysinfo;
+#[cfg(feature = "serialport")]
 pub mod os_serialport;
-pub mod os_path;
\ No newline at end of file
+#[cfg(feature = "path")]
+pub mod os_path;
+/// 常用类型的统一导出：`use ostring_base::prelude::*;`
+pub mod prelude;
@@ -1,80 +1,410 @@
-use anyhow::{anyhow, Result};
-use auto_launch::{AutoLaunch, AutoLaunchBuilder};
-use std::sync::{Arc, Mutex, OnceLock};
+use auto_launch::{AutoLaunch, AutoLaunchBuilder, MacOSLaunchMode, WindowsEnableMode};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, Mutex, MutexGuard, TryLockError};
 use std::env::current_exe;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 获取实例锁的最长等待时间
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+/// 获取实例锁失败后的重试间隔
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 自动启动管理的错误类型
+///
+/// 实现了 `std::error::Error`，可以直接通过 `?` 转换为 `anyhow::Error`
+#[derive(Debug)]
+pub enum AutoLaunchError {
+    /// 在限定时间内未能获取实例锁
+    LockTimeout,
+    /// 实例已初始化，无法重复设置
+    AlreadyInitialized,
+    /// 无法解析当前可执行文件的名称或路径
+    ExeResolution(String),
+    /// 注册到所有用户需要管理员权限
+    AdminRequired,
+    /// `for_name` 指定的应用名称为空
+    EmptyName,
+    /// auto_launch后端（注册表、LaunchAgent、autostart文件等）操作失败
+    Backend(auto_launch::Error),
+    /// 异步任务执行失败
+    #[cfg(feature = "tokio")]
+    Join(tokio::task::JoinError),
+}
+
+impl fmt::Display for AutoLaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LockTimeout => write!(f, "获取锁超时"),
+            Self::AlreadyInitialized => write!(f, "AutoLaunch实例已初始化，无法重复设置"),
+            Self::ExeResolution(msg) => write!(f, "{msg}"),
+            Self::AdminRequired => write!(f, "为所有用户注册自动启动需要管理员权限"),
+            Self::EmptyName => write!(f, "应用名称不能为空"),
+            Self::Backend(e) => write!(f, "自动启动操作失败: {e}"),
+            #[cfg(feature = "tokio")]
+            Self::Join(e) => write!(f, "异步任务执行失败: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AutoLaunchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Backend(e) => Some(e),
+            #[cfg(feature = "tokio")]
+            Self::Join(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<auto_launch::Error> for AutoLaunchError {
+    fn from(e: auto_launch::Error) -> Self {
+        Self::Backend(e)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl From<tokio::task::JoinError> for AutoLaunchError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        Self::Join(e)
+    }
+}
+
+type Result<T> = std::result::Result<T, AutoLaunchError>;
+
+/// 在限定时间内循环尝试获取锁
+///
+/// 锁已中毒时取出内部值并清除中毒标记，避免某个线程panic后自动启动功能在整个进程内不可用
+fn lock_with_timeout<T>(mutex: &Mutex<T>, timeout: Duration) -> Result<MutexGuard<'_, T>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match mutex.try_lock() {
+            Ok(guard) => return Ok(guard),
+            Err(TryLockError::Poisoned(poisoned)) => {
+                mutex.clear_poison();
+                return Ok(poisoned.into_inner());
+            }
+            Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                thread::sleep(LOCK_RETRY_INTERVAL);
+            }
+            Err(TryLockError::WouldBlock) => return Err(AutoLaunchError::LockTimeout),
+        }
+    }
+}
+
+/// macOS下自动启动的实现方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AutoLaunchMacBackend {
+    /// 通过AppleScript添加登录项（默认）
+    #[default]
+    AppleScript,
+    /// 在 `~/Library/LaunchAgents` 下写入plist
+    LaunchAgent,
+}
+
+/// 自动启动的注册范围，仅在Windows下生效
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AutoLaunchScope {
+    /// 仅当前用户（`HKCU`，默认）
+    #[default]
+    CurrentUser,
+    /// 所有用户（`HKLM`），需要管理员权限
+    AllUsers,
+}
+
+/// macOS LaunchAgent plist的附加配置，仅在macOS下生效
+///
+/// auto-launch生成的plist固定包含 `Label`（应用名称）、`ProgramArguments`（路径和参数）
+/// 和 `RunAtLoad = true`，这些键无法修改或关闭；这里的选项会追加到plist的 `<dict>` 中
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlistOptions {
+    /// `KeepAlive`，None时不写入（launchd默认不保活）
+    pub keep_alive: Option<bool>,
+    /// `WorkingDirectory`，启动时的工作目录
+    pub working_directory: Option<String>,
+    /// `AssociatedBundleIdentifiers`，在系统设置的登录项中关联显示的bundle id
+    pub bundle_identifiers: Vec<String>,
+    /// 原样追加的plist片段，如 `<key>ProcessType</key><string>Interactive</string>`，不做校验
+    pub extra_config: String,
+}
+
+impl PlistOptions {
+    /// 生成追加到plist中的XML片段
+    fn agent_extra_config(&self) -> String {
+        let mut config = String::new();
+        if let Some(keep_alive) = self.keep_alive {
+            config.push_str(&format!("<key>KeepAlive</key><{keep_alive}/>"));
+        }
+        if let Some(dir) = &self.working_directory {
+            config.push_str(&format!("<key>WorkingDirectory</key><string>{}</string>", xml_escape(dir)));
+        }
+        config.push_str(&self.extra_config);
+        config
+    }
+}
+
+/// 可执行文件向上三级的目录以 `.app` 结尾时返回该目录
+fn app_bundle_of(exe: &Path) -> Option<PathBuf> {
+    let bundle = exe.parent()?.parent()?.parent()?;
+    (bundle.extension()? == "app").then(|| bundle.to_path_buf())
+}
+
+/// 转义plist字符串中的XML特殊字符
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 缓存的AutoLaunch实例，以及创建它的构建参数
+///
+/// 保留builder是为了在修改启动参数时重建实例，不丢失平台相关的设置
+struct LaunchEntry {
+    builder: AutoLaunchBuilder,
+    auto: AutoLaunch,
+}
+
+/// 默认实例在实例表中的键，`for_name` 不接受空名称，因此不会与具名实例冲突
+const DEFAULT_KEY: &str = "";
 
 /// 自动启动管理模块
 pub struct AutoLaunchManager;
 
 impl AutoLaunchManager {
-    /// 保存AutoLaunch实例的静态OnceLock
-    fn auto_launch_instance() -> &'static OnceLock<Arc<Mutex<AutoLaunch>>> {
-        static AUTO_LAUNCH: OnceLock<Arc<Mutex<AutoLaunch>>> = OnceLock::new();
+    /// 保存AutoLaunch实例的静态Mutex，以应用名称为键，默认实例的键为 [`DEFAULT_KEY`]
+    fn auto_launch_instances() -> &'static Mutex<HashMap<String, LaunchEntry>> {
+        static AUTO_LAUNCH: LazyLock<Mutex<HashMap<String, LaunchEntry>>> = LazyLock::new(Default::default);
         &AUTO_LAUNCH
     }
 
+    /// 获取实例表的锁，最多等待 `LOCK_TIMEOUT`
+    fn lock_instance() -> Result<MutexGuard<'static, HashMap<String, LaunchEntry>>> {
+        lock_with_timeout(Self::auto_launch_instances(), LOCK_TIMEOUT)
+    }
+
     /// 获取AutoLaunch实例，如果不存在则创建
-    fn get_or_init_autolaunch() -> Result<AutoLaunch> {
-        // 如果已初始化，直接返回克隆
-        if let Some(auto_launch) = Self::auto_launch_instance().get() {
-            let guard = auto_launch.lock().map_err(|_| anyhow!("获取锁失败"))?;
-            return Ok(guard.clone());
+    ///
+    /// * `args` - 为Some时使用指定的启动参数，与缓存实例的参数不同时替换缓存实例；
+    ///   为None时沿用缓存实例的参数（首次初始化时为空）
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, err))]
+    fn get_or_init_autolaunch(args: Option<&[String]>) -> Result<AutoLaunch> {
+        let mut entries = Self::lock_instance()?;
+        let entry = Self::get_or_init_in(&mut entries, DEFAULT_KEY, args)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            app_name = entry.auto.get_app_name(),
+            app_path = entry.auto.get_app_path(),
+            args = ?entry.auto.get_args(),
+            "自动启动实例已就绪"
+        );
+        Ok(entry.auto.clone())
+    }
+
+    /// 在已持有锁的实例表中获取或创建 `key` 对应的AutoLaunch实例
+    ///
+    /// 具名实例首次创建时使用 `key` 作为应用名称，路径仍从当前可执行文件推导
+    fn get_or_init_in<'a>(
+        entries: &'a mut HashMap<String, LaunchEntry>,
+        key: &str,
+        args: Option<&[String]>,
+    ) -> Result<&'a mut LaunchEntry> {
+        let entry = match entries.entry(key.to_string()) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            // 需要初始化
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                let mut builder = Self::builder_from_current_exe()?;
+                if key != DEFAULT_KEY {
+                    builder.set_app_name(key);
+                }
+                entry.insert(Self::build_entry(builder)?)
+            }
+        };
+
+        // 参数不同时用新参数重建
+        if let Some(args) = args.filter(|args| entry.auto.get_args() != *args) {
+            let mut builder = entry.builder.clone();
+            builder.set_args(args);
+            *entry = Self::build_entry(builder)?;
         }
-        
-        // 需要初始化
-        let app_exe = current_exe()?;
+
+        Ok(entry)
+    }
+
+    /// 创建带有默认平台设置的builder
+    ///
+    /// macOS默认使用AppleScript登录项，Windows默认只写入当前用户，
+    /// 不会在有管理员权限时自动改写到 `HKLM`
+    fn new_builder() -> AutoLaunchBuilder {
+        let mut builder = AutoLaunchBuilder::new();
+        builder
+            .set_macos_launch_mode(MacOSLaunchMode::AppleScript)
+            .set_windows_enable_mode(WindowsEnableMode::CurrentUser);
+        builder
+    }
+
+    /// 根据builder创建缓存项
+    fn build_entry(builder: AutoLaunchBuilder) -> Result<LaunchEntry> {
+        let auto = builder.build()?;
+        Ok(LaunchEntry { builder, auto })
+    }
+
+    /// 根据当前可执行文件推导应用名称和路径，返回对应的builder
+    fn builder_from_current_exe() -> Result<AutoLaunchBuilder> {
+        let app_exe = current_exe()
+            .map_err(|e| AutoLaunchError::ExeResolution(format!("无法获取当前可执行文件: {e}")))?;
         let app_name = app_exe
             .file_stem()
             .and_then(|f| f.to_str())
-            .ok_or(anyhow!("无法获取应用程序名称"))?;
+            .ok_or(AutoLaunchError::ExeResolution("无法获取应用程序名称".into()))?;
 
         let app_path = app_exe
             .as_os_str()
             .to_str()
-            .ok_or(anyhow!("无法获取应用程序路径"))?
+            .ok_or(AutoLaunchError::ExeResolution("无法获取应用程序路径".into()))?
             .to_string();
 
         #[cfg(target_os = "windows")]
         let app_path = format!("\"{app_path}\"");
 
         #[cfg(target_os = "macos")]
-        let app_path = (|| -> Option<String> {
-            let path = std::path::PathBuf::from(&app_path);
-            let path = path.parent()?.parent()?.parent()?;
-            let extension = path.extension()?.to_str()?;
-            match extension == "app" {
-                true => Some(path.as_os_str().to_str()?.to_string()),
-                false => None,
-            }
-        })()
-        .unwrap_or(app_path);
+        let app_path = app_bundle_of(Path::new(&app_path))
+            .and_then(|bundle| bundle.to_str().map(str::to_string))
+            .unwrap_or(app_path);
 
-        // Linux平台专用配置，注意我们移除了对外部crate的依赖
-        #[cfg(target_os = "linux")]
-        let app_path = app_path; // 在Linux下直接使用可执行文件路径
-        
-        let auto = AutoLaunchBuilder::new()
-            .set_app_name(app_name)
-            .set_app_path(&app_path)
-            .build()?;
-        
-        // 使用OnceLock保存新创建的AutoLaunch
-        let auto_arc = Arc::new(Mutex::new(auto.clone()));
-        match Self::auto_launch_instance().set(auto_arc) {
-            Ok(_) => Ok(auto),
-            Err(_) => {
-                // 如果在我们初始化的过程中，其他线程已经初始化了
-                // 使用已存在的值
-                let existing = Self::auto_launch_instance().get().unwrap();
-                let guard = existing.lock().map_err(|_| anyhow!("获取锁失败"))?;
-                Ok(guard.clone())
-            }
+        // Linux下直接使用可执行文件路径
+        let mut builder = Self::new_builder();
+        builder.set_app_name(app_name).set_app_path(&app_path);
+        Ok(builder)
+    }
+
+    /// 当前可执行文件所在的macOS `.app` 包目录，不在包内时返回None
+    ///
+    /// 按 `<名称>.app/Contents/MacOS/<可执行文件>` 的标准结构判断，可用于定位包内资源。
+    /// 其他平台没有 `.app` 包，通常返回None
+    pub fn bundle_path() -> Option<PathBuf> {
+        app_bundle_of(&current_exe().ok()?)
+    }
+
+    /// 用指定的builder初始化实例，如果实例已初始化则返回错误
+    fn init_entry(builder: AutoLaunchBuilder) -> Result<()> {
+        let mut entries = Self::lock_instance()?;
+        if entries.contains_key(DEFAULT_KEY) {
+            return Err(AutoLaunchError::AlreadyInitialized);
         }
+
+        entries.insert(DEFAULT_KEY.to_string(), Self::build_entry(builder)?);
+        Ok(())
+    }
+
+    /// 使用指定的应用名称和路径初始化AutoLaunch实例
+    ///
+    /// 必须在首次隐式初始化之前调用，如果实例已初始化则返回错误
+    ///
+    /// * `name` - 注册的应用名称
+    /// * `path` - 自动启动时执行的程序路径
+    pub fn init_with(name: &str, path: &str) -> Result<()> {
+        let mut builder = Self::new_builder();
+        builder.set_app_name(name).set_app_path(path);
+        Self::init_entry(builder)
+    }
+
+    /// 使用指定的macOS实现方式初始化AutoLaunch实例，名称和路径仍从当前可执行文件推导
+    ///
+    /// 必须在首次隐式初始化之前调用，如果实例已初始化则返回错误。
+    /// 非macOS平台会接受该参数但不产生任何影响。
+    ///
+    /// * `backend` - 使用AppleScript登录项或LaunchAgent
+    pub fn init_with_backend(backend: AutoLaunchMacBackend) -> Result<()> {
+        let mut builder = Self::builder_from_current_exe()?;
+        builder.set_macos_launch_mode(match backend {
+            AutoLaunchMacBackend::AppleScript => MacOSLaunchMode::AppleScript,
+            AutoLaunchMacBackend::LaunchAgent => MacOSLaunchMode::LaunchAgent,
+        });
+        Self::init_entry(builder)
+    }
+
+    /// 使用LaunchAgent和附加的plist配置初始化AutoLaunch实例，名称和路径仍从当前可执行文件推导
+    ///
+    /// 必须在首次隐式初始化之前调用，如果实例已初始化则返回错误。
+    /// 非macOS平台会接受该参数但不产生任何影响。
+    ///
+    /// * `extra` - 追加到plist的配置，可支持的键见 [`PlistOptions`]
+    pub fn init_with_plist(extra: PlistOptions) -> Result<()> {
+        let mut builder = Self::builder_from_current_exe()?;
+        builder
+            .set_macos_launch_mode(MacOSLaunchMode::LaunchAgent)
+            .set_bundle_identifiers(&extra.bundle_identifiers)
+            .set_agent_extra_config(&extra.agent_extra_config());
+        Self::init_entry(builder)
+    }
+
+    /// 使用指定的注册范围初始化AutoLaunch实例，名称和路径仍从当前可执行文件推导
+    ///
+    /// 必须在首次隐式初始化之前调用，如果实例已初始化则返回错误。
+    /// 选择 `AllUsers` 时写入 `HKLM`，进程没有管理员权限时启用/禁用会返回
+    /// `AutoLaunchError::AdminRequired`，不会退回到 `HKCU`。
+    /// macOS和Linux下该参数只是提示，不产生任何影响。
+    ///
+    /// * `scope` - 注册到当前用户或所有用户
+    pub fn init_with_scope(scope: AutoLaunchScope) -> Result<()> {
+        let mut builder = Self::builder_from_current_exe()?;
+        builder.set_windows_enable_mode(match scope {
+            AutoLaunchScope::CurrentUser => WindowsEnableMode::CurrentUser,
+            AutoLaunchScope::AllUsers => WindowsEnableMode::System,
+        });
+        Self::init_entry(builder)
+    }
+
+    /// 清除缓存的AutoLaunch实例，包括 [`AutoLaunchManager::for_name`] 创建的具名实例
+    ///
+    /// 下次调用时会根据当前的 `current_exe()` 重新初始化，
+    /// 适用于可执行文件被移动或原地更新之后。已写入系统的注册项不受影响。
+    pub fn reset() -> Result<()> {
+        Self::lock_instance()?.clear();
+        Ok(())
+    }
+
+    /// 获取以 `name` 注册的具名自动启动项，用于在一个进程中管理多个互不影响的注册项
+    ///
+    /// 具名实例与默认实例分别缓存，首次使用时创建，路径默认从当前可执行文件推导
+    pub fn for_name(name: &str) -> NamedAutoLaunch {
+        NamedAutoLaunch { name: name.to_string(), path: None }
+    }
+
+    /// 批量启用或禁用多个具名自动启动项，遇到第一个错误时停止并返回该错误
+    ///
+    /// 整个过程持有实例表的锁，不会与其他注册操作交错执行
+    pub fn update_launch_many(names: &[&str], enable: bool) -> Result<()> {
+        let mut entries = Self::lock_instance()?;
+        for name in names {
+            let entry = Self::for_name(name).entry_in(&mut entries)?;
+            Self::apply_launch(&entry.auto, enable)?;
+        }
+        Ok(())
+    }
+
+    /// 获取注册使用的应用名称，如果实例未初始化则先初始化
+    pub fn app_name() -> Result<String> {
+        let auto = Self::get_or_init_autolaunch(None)?;
+        Ok(auto.get_app_name().to_string())
+    }
+
+    /// 获取注册使用的应用路径，如果实例未初始化则先初始化
+    ///
+    /// 返回的是最终写入系统的路径（例如macOS下解析后的 `.app` 目录）
+    pub fn app_path() -> Result<String> {
+        let auto = Self::get_or_init_autolaunch(None)?;
+        Ok(auto.get_app_path().to_string())
     }
 
     /// 检查自动启动是否已启用
     pub fn is_enabled() -> Result<bool> {
-        let auto = Self::get_or_init_autolaunch()?;
+        let auto = Self::get_or_init_autolaunch(None)?;
         Ok(auto.is_enabled()?)
     }
 
@@ -82,14 +412,289 @@ impl AutoLaunchManager {
     /// 
     /// * `enable` - 设置为true启用自动启动，false禁用自动启动
     pub fn update_launch(enable: bool) -> Result<()> {
-        let auto = Self::get_or_init_autolaunch()?;
-        
-        match enable {
-            true => auto.enable()?,
-            false => auto.disable()?,
+        let auto = Self::get_or_init_autolaunch(None)?;
+        Self::apply_launch(&auto, enable)
+    }
+
+    /// 使用指定的启动参数更新自动启动状态
+    ///
+    /// 参数会在首次初始化时传给AutoLaunchBuilder，并保存在缓存的实例中，
+    /// 之后的 `is_enabled` 与 `update_launch` 都使用同一份注册信息。
+    /// 如果与缓存实例的参数不同，会用新参数替换缓存的实例，
+    /// 启用时再次写入的注册项也会使用新参数。
+    ///
+    /// * `enable` - 设置为true启用自动启动，false禁用自动启动
+    /// * `args` - 自动启动时传给程序的参数，例如 `--minimized`
+    pub fn update_launch_with_args(enable: bool, args: &[String]) -> Result<()> {
+        let auto = Self::get_or_init_autolaunch(Some(args))?;
+        Self::apply_launch(&auto, enable)
+    }
+
+    /// 切换自动启动状态，返回切换后的状态
+    ///
+    /// 读取当前状态和写入相反状态期间一直持有实例的锁，并发的toggle调用不会交错执行
+    pub fn toggle() -> Result<bool> {
+        let mut entries = Self::lock_instance()?;
+        let auto = &Self::get_or_init_in(&mut entries, DEFAULT_KEY, None)?.auto;
+
+        let enable = !auto.is_enabled()?;
+        Self::apply_launch(auto, enable)?;
+        Ok(enable)
+    }
+
+    /// 检查自动启动是否已启用，并且注册项中保存的命令仍指向实例的程序路径
+    ///
+    /// 实例路径默认由 `current_exe()` 推导。应用更新或移动后注册项可能仍指向旧路径，
+    /// 此时返回 `Ok(false)`，可以调用 `repair` 重新注册。无法读取注册项内容时同样视为无效。
+    pub fn is_enabled_and_valid() -> Result<bool> {
+        let mut entries = Self::lock_instance()?;
+        let entry = Self::get_or_init_in(&mut entries, DEFAULT_KEY, None)?;
+
+        if !entry.auto.is_enabled()? {
+            return Ok(false);
+        }
+
+        let app_path = entry.auto.get_app_path();
+        let valid = Self::registered_command(entry).is_some_and(|command| {
+            command == app_path || command.starts_with(&format!("{app_path} "))
+        });
+        Ok(valid)
+    }
+
+    /// 先禁用再重新启用自动启动，使注册项指向实例当前的程序路径和参数
+    pub fn repair() -> Result<()> {
+        let mut entries = Self::lock_instance()?;
+        let auto = &Self::get_or_init_in(&mut entries, DEFAULT_KEY, None)?.auto;
+
+        Self::apply_launch(auto, false)?;
+        Self::apply_launch(auto, true)
+    }
+
+    /// 当前环境是否能管理自动启动项，不会初始化或修改任何状态
+    ///
+    /// Windows和macOS总是返回true。Linux使用XDG autostart，只有桌面会话会读取
+    /// `~/.config/autostart`：存在该目录，或者设置了 `XDG_CURRENT_DESKTOP` / `DESKTOP_SESSION`
+    /// 时视为支持，无法获取用户主目录时返回false
+    pub fn is_supported() -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            let Some(home) = dirs::home_dir() else {
+                return false;
+            };
+            let has_session = ["XDG_CURRENT_DESKTOP", "DESKTOP_SESSION"]
+                .iter()
+                .any(|key| std::env::var_os(key).is_some_and(|value| !value.is_empty()));
+            has_session || home.join(".config").join("autostart").is_dir()
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            true
+        }
+    }
+
+    /// 预览启用或禁用时将执行的操作，不会写入或删除任何注册项
+    ///
+    /// 启用时返回 "写入 <位置>: <命令>"，禁用时返回 "删除 <位置>"。
+    /// 实例未初始化时会按默认方式初始化（只读）以计算路径
+    pub fn preview(enable: bool) -> Result<String> {
+        let mut entries = Self::lock_instance()?;
+        let entry = Self::get_or_init_in(&mut entries, DEFAULT_KEY, None)?;
+        let location = Self::registration_location(entry);
+        Ok(match enable {
+            true => {
+                let command = format!("{} {}", entry.auto.get_app_path(), entry.auto.get_args().join(" "));
+                format!("写入 {location}: {}", command.trim_end())
+            }
+            false => format!("删除 {location}"),
+        })
+    }
+
+    /// Linux autostart文件路径
+    #[cfg(target_os = "linux")]
+    fn registration_location(entry: &LaunchEntry) -> String {
+        let name = entry.auto.get_app_name();
+        match dirs::home_dir() {
+            Some(home) => home.join(".config").join("autostart").join(format!("{name}.desktop"))
+                .to_string_lossy()
+                .to_string(),
+            None => format!("~/.config/autostart/{name}.desktop"),
+        }
+    }
+
+    /// Windows注册表Run键下的值
+    #[cfg(target_os = "windows")]
+    fn registration_location(entry: &LaunchEntry) -> String {
+        let root = match entry.builder.windows_enable_mode {
+            WindowsEnableMode::System => "HKLM",
+            _ => "HKCU",
         };
+        format!(r"{root}\SOFTWARE\Microsoft\Windows\CurrentVersion\Run\{}", entry.auto.get_app_name())
+    }
 
-        Ok(())
+    /// macOS LaunchAgent plist路径，或AppleScript登录项名称
+    #[cfg(target_os = "macos")]
+    fn registration_location(entry: &LaunchEntry) -> String {
+        let name = entry.auto.get_app_name();
+        if entry.builder.macos_launch_mode != MacOSLaunchMode::LaunchAgent {
+            return format!("登录项 \"{name}\"");
+        }
+        match dirs::home_dir() {
+            Some(home) => home.join("Library").join("LaunchAgents").join(format!("{name}.plist"))
+                .to_string_lossy()
+                .to_string(),
+            None => format!("~/Library/LaunchAgents/{name}.plist"),
+        }
+    }
+
+    /// 读取Linux autostart文件中的Exec命令
+    #[cfg(target_os = "linux")]
+    fn registered_command(entry: &LaunchEntry) -> Option<String> {
+        let file = dirs::home_dir()?
+            .join(".config")
+            .join("autostart")
+            .join(format!("{}.desktop", entry.auto.get_app_name()));
+        let content = std::fs::read_to_string(file).ok()?;
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("Exec="))
+            .map(|command| command.trim_end().to_string())
+    }
+
+    /// 读取注册表 `HKCU`（或 `HKLM`）`\...\Run` 中保存的命令
+    #[cfg(target_os = "windows")]
+    fn registered_command(entry: &LaunchEntry) -> Option<String> {
+        let root = match entry.builder.windows_enable_mode {
+            WindowsEnableMode::System => windows_registry::LOCAL_MACHINE,
+            _ => windows_registry::CURRENT_USER,
+        };
+
+        root.open(r"SOFTWARE\Microsoft\Windows\CurrentVersion\Run")
+            .ok()?
+            .get_string(entry.auto.get_app_name())
+            .ok()
+    }
+
+    /// 读取LaunchAgent plist中的程序路径，或AppleScript登录项的路径
+    #[cfg(target_os = "macos")]
+    fn registered_command(entry: &LaunchEntry) -> Option<String> {
+        let name = entry.auto.get_app_name();
+        if entry.builder.macos_launch_mode == MacOSLaunchMode::LaunchAgent {
+            let file = dirs::home_dir()?
+                .join("Library")
+                .join("LaunchAgents")
+                .join(format!("{name}.plist"));
+            let content = std::fs::read_to_string(file).ok()?;
+            let program = content.split("<array>").nth(1)?.split("<string>").nth(1)?;
+            program.split("</string>").next().map(str::to_string)
+        } else {
+            let script = format!("tell application \"System Events\" to get the path of login item \"{name}\"");
+            let output = std::process::Command::new("osascript")
+                .args(["-e", &script])
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        }
+    }
+
+    /// 其他平台无法读取注册项
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    fn registered_command(_entry: &LaunchEntry) -> Option<String> {
+        None
+    }
+
+    /// 根据enable启用或禁用自动启动
+    fn apply_launch(auto: &AutoLaunch, enable: bool) -> Result<()> {
+        let result = match enable {
+            true => auto.enable(),
+            false => auto.disable(),
+        };
+
+        result.map_err(launch_error)
+    }
+}
+
+/// 转换启用/禁用时的后端错误，Windows上权限被拒绝（只有写入HKLM才会出现）转换为 `AdminRequired`
+///
+/// auto_launch通过 `windows_result::Error` 得到的io错误携带的是HRESULT（`E_ACCESSDENIED`），
+/// 标准库不会将其识别为 `PermissionDenied`，因此先比较原始错误码，再退回到错误类型
+fn launch_error(e: auto_launch::Error) -> AutoLaunchError {
+    #[cfg(target_os = "windows")]
+    if let auto_launch::Error::Io(io) = &e {
+        const E_ACCESSDENIED: i32 = 0x8007_0005_u32 as i32;
+        if io.raw_os_error() == Some(E_ACCESSDENIED) || io.kind() == std::io::ErrorKind::PermissionDenied {
+            return AutoLaunchError::AdminRequired;
+        }
+    }
+    e.into()
+}
+
+/// 具名自动启动项，由 [`AutoLaunchManager::for_name`] 创建
+///
+/// 只保存名称和可选的路径，实例缓存在 `AutoLaunchManager` 中，多个句柄可以指向同一注册项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedAutoLaunch {
+    name: String,
+    path: Option<String>,
+}
+
+impl NamedAutoLaunch {
+    /// 指定自动启动时执行的程序路径，与缓存实例的路径不同时替换缓存实例
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// 注册使用的应用名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 检查该注册项是否已启用
+    pub fn is_enabled(&self) -> Result<bool> {
+        let mut entries = AutoLaunchManager::lock_instance()?;
+        Ok(self.entry_in(&mut entries)?.auto.is_enabled()?)
+    }
+
+    /// 更新该注册项的自动启动状态
+    ///
+    /// * `enable` - 设置为true启用自动启动，false禁用自动启动
+    pub fn update_launch(&self, enable: bool) -> Result<()> {
+        let mut entries = AutoLaunchManager::lock_instance()?;
+        AutoLaunchManager::apply_launch(&self.entry_in(&mut entries)?.auto, enable)
+    }
+
+    /// 在已持有锁的实例表中获取或创建该注册项的实例
+    fn entry_in<'a>(&self, entries: &'a mut HashMap<String, LaunchEntry>) -> Result<&'a mut LaunchEntry> {
+        if self.name.is_empty() {
+            return Err(AutoLaunchError::EmptyName);
+        }
+        let entry = AutoLaunchManager::get_or_init_in(entries, &self.name, None)?;
+        if let Some(path) = self.path.as_deref().filter(|path| entry.auto.get_app_path() != *path) {
+            let mut builder = entry.builder.clone();
+            builder.set_app_path(path);
+            *entry = AutoLaunchManager::build_entry(builder)?;
+        }
+        Ok(entry)
+    }
+}
+
+/// 异步接口，在阻塞线程池中执行注册表/文件系统操作，避免阻塞tokio运行时
+#[cfg(feature = "tokio")]
+impl AutoLaunchManager {
+    /// 异步检查自动启动是否已启用
+    pub async fn is_enabled_async() -> Result<bool> {
+        tokio::task::spawn_blocking(Self::is_enabled).await?
+    }
+
+    /// 异步更新自动启动状态
+    ///
+    /// * `enable` - 设置为true启用自动启动，false禁用自动启动
+    pub async fn update_launch_async(enable: bool) -> Result<()> {
+        tokio::task::spawn_blocking(move || Self::update_launch(enable)).await?
     }
 }
 
@@ -97,54 +702,292 @@ impl AutoLaunchManager {
 mod tests {
     use super::*;
 
+    /// 自动启动实例是进程级共享状态，相关测试需要串行执行
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+
     #[test]
     fn test_autolaunch_manager() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
         // 验证初始状态
-        assert!(AutoLaunchManager::auto_launch_instance().get().is_none(), "初始状态应为None");
+        assert!(AutoLaunchManager::lock_instance().unwrap().get(DEFAULT_KEY).is_none(), "初始状态应为None");
         
         // 测试启用自动启动（同时会初始化）
         assert!(AutoLaunchManager::update_launch(true).is_ok(), "启用自动启动应该成功");
         
         // 验证已被初始化
-        assert!(AutoLaunchManager::auto_launch_instance().get().is_some(), "auto_launch 应该已初始化");
+        assert!(AutoLaunchManager::lock_instance().unwrap().contains_key(DEFAULT_KEY), "auto_launch 应该已初始化");
+
+        // 已初始化后不能再指定名称和路径
+        assert!(
+            matches!(AutoLaunchManager::init_with("other", "/usr/bin/other"), Err(AutoLaunchError::AlreadyInitialized)),
+            "重复初始化应该返回错误"
+        );
         
+        // 测试带参数启用自动启动，参数应保存在缓存的实例中
+        let args = vec!["--minimized".to_string()];
+        assert!(AutoLaunchManager::update_launch_with_args(true, &args).is_ok(), "带参数启用自动启动应该成功");
+        assert_eq!(AutoLaunchManager::get_or_init_autolaunch(None).unwrap().get_args(), args.as_slice());
+
         // 测试禁用自动启动
         assert!(AutoLaunchManager::update_launch(false).is_ok(), "禁用自动启动应该成功");
         
         // 测试检查是否启用
         let is_enabled = AutoLaunchManager::is_enabled();
         assert!(is_enabled.is_ok(), "检查自动启动状态应该成功");
+
+        // 测试切换自动启动状态
+        let before = is_enabled.unwrap();
+        assert_eq!(AutoLaunchManager::toggle().unwrap(), !before, "切换后应返回相反状态");
+        assert_eq!(AutoLaunchManager::is_enabled().unwrap(), !before);
+        assert_eq!(AutoLaunchManager::toggle().unwrap(), before, "再次切换应恢复原状态");
     }
 
-    #[cfg(target_os = "macos")]
     #[test]
-    fn test_macos_app_path_resolution() {
-        let test_paths = vec![
-            ("/Applications/MyApp.app/Contents/MacOS/binary", "/Applications/MyApp.app"),
-            ("/usr/local/bin/myapp", "/usr/local/bin/myapp"),
+    fn test_autolaunch_reset() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        // 使用旧路径初始化
+        AutoLaunchManager::reset().unwrap();
+        AutoLaunchManager::init_with("ostring_reset_test", "/tmp/old/ostring_reset_test").unwrap();
+        assert_eq!(AutoLaunchManager::app_name().unwrap(), "ostring_reset_test");
+        assert_eq!(AutoLaunchManager::app_path().unwrap(), "/tmp/old/ostring_reset_test");
+
+        // 重置后应根据当前可执行文件重新初始化
+        AutoLaunchManager::reset().unwrap();
+        assert!(AutoLaunchManager::lock_instance().unwrap().is_empty(), "重置后应为空");
+        let auto = AutoLaunchManager::get_or_init_autolaunch(None).unwrap();
+        let expected = AutoLaunchManager::builder_from_current_exe().unwrap();
+        assert_eq!(Some(auto.get_app_path()), expected.app_path.as_deref());
+
+        // 恢复初始状态
+        AutoLaunchManager::reset().unwrap();
+    }
+
+    #[test]
+    fn test_autolaunch_is_supported() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        AutoLaunchManager::reset().unwrap();
+        let supported = AutoLaunchManager::is_supported();
+        if !cfg!(target_os = "linux") {
+            assert!(supported);
+        }
+        assert!(AutoLaunchManager::lock_instance().unwrap().is_empty(), "检查支持情况不应初始化实例");
+    }
+
+    #[test]
+    fn test_autolaunch_init_with_backend() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        AutoLaunchManager::reset().unwrap();
+        assert!(AutoLaunchManager::init_with_backend(AutoLaunchMacBackend::LaunchAgent).is_ok());
+        assert!(
+            matches!(
+                AutoLaunchManager::init_with_backend(AutoLaunchMacBackend::AppleScript),
+                Err(AutoLaunchError::AlreadyInitialized)
+            ),
+            "初始化后不能再修改实现方式"
+        );
+
+        // 修改启动参数后应保留实现方式
+        let args = vec!["--autostart".to_string()];
+        AutoLaunchManager::get_or_init_autolaunch(Some(&args)).unwrap();
+        let entries = AutoLaunchManager::lock_instance().unwrap();
+        let entry = &entries[DEFAULT_KEY];
+        assert_eq!(entry.builder.macos_launch_mode, MacOSLaunchMode::LaunchAgent);
+        assert_eq!(entry.auto.get_args(), args.as_slice());
+        drop(entries);
+
+        AutoLaunchManager::reset().unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_autolaunch_valid_and_repair() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let name = "ostring_valid_test";
+
+        AutoLaunchManager::reset().unwrap();
+        AutoLaunchManager::init_with(name, "/tmp/ostring_valid_test/v1/app").unwrap();
+        AutoLaunchManager::update_launch(true).unwrap();
+        assert!(AutoLaunchManager::is_enabled_and_valid().unwrap(), "刚注册的路径应该有效");
+
+        // 模拟应用更新后路径变化
+        AutoLaunchManager::reset().unwrap();
+        AutoLaunchManager::init_with(name, "/tmp/ostring_valid_test/v2/app").unwrap();
+        assert!(AutoLaunchManager::is_enabled().unwrap());
+        assert!(!AutoLaunchManager::is_enabled_and_valid().unwrap(), "路径变化后应该无效");
+
+        AutoLaunchManager::repair().unwrap();
+        assert!(AutoLaunchManager::is_enabled_and_valid().unwrap(), "修复后应该有效");
+
+        AutoLaunchManager::update_launch(false).unwrap();
+        assert!(!AutoLaunchManager::is_enabled_and_valid().unwrap(), "未启用时应返回false");
+        AutoLaunchManager::reset().unwrap();
+    }
+
+    #[test]
+    fn test_autolaunch_init_with_plist() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let options = PlistOptions {
+            keep_alive: Some(false),
+            working_directory: Some("/Users/me/A&B".to_string()),
+            bundle_identifiers: vec!["com.example.menubar".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            options.agent_extra_config(),
+            "<key>KeepAlive</key><false/><key>WorkingDirectory</key><string>/Users/me/A&amp;B</string>"
+        );
+
+        AutoLaunchManager::reset().unwrap();
+        AutoLaunchManager::init_with_plist(options).unwrap();
+        {
+            let entries = AutoLaunchManager::lock_instance().unwrap();
+            let builder = &entries[DEFAULT_KEY].builder;
+            assert_eq!(builder.macos_launch_mode, MacOSLaunchMode::LaunchAgent);
+            assert_eq!(builder.bundle_identifiers.as_deref(), Some(&["com.example.menubar".to_string()][..]));
+        }
+        assert!(AutoLaunchManager::init_with_plist(PlistOptions::default()).is_err(), "重复初始化应返回错误");
+
+        AutoLaunchManager::reset().unwrap();
+    }
+
+    #[test]
+    fn test_autolaunch_preview() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        AutoLaunchManager::reset().unwrap();
+        AutoLaunchManager::init_with("ostring_preview_test", "/opt/preview/ostring_preview_test").unwrap();
+        AutoLaunchManager::update_launch_with_args(false, &["--minimized".to_string()]).unwrap();
+
+        let enable = AutoLaunchManager::preview(true).unwrap();
+        assert!(enable.starts_with("写入 "), "{enable}");
+        assert!(enable.contains("ostring_preview_test"), "{enable}");
+        assert!(enable.ends_with("/opt/preview/ostring_preview_test --minimized"), "{enable}");
+
+        let disable = AutoLaunchManager::preview(false).unwrap();
+        assert!(disable.starts_with("删除 "), "{disable}");
+        assert!(!AutoLaunchManager::is_enabled().unwrap(), "预览不应写入注册项");
+
+        AutoLaunchManager::reset().unwrap();
+    }
+
+    #[test]
+    fn test_autolaunch_init_with_scope() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        AutoLaunchManager::reset().unwrap();
+        assert!(AutoLaunchManager::init_with_scope(AutoLaunchScope::AllUsers).is_ok());
+        let entries = AutoLaunchManager::lock_instance().unwrap();
+        assert_eq!(entries[DEFAULT_KEY].builder.windows_enable_mode, WindowsEnableMode::System);
+        drop(entries);
+
+        assert!(
+            matches!(
+                AutoLaunchManager::init_with_scope(AutoLaunchScope::CurrentUser),
+                Err(AutoLaunchError::AlreadyInitialized)
+            ),
+            "初始化后不能再修改注册范围"
+        );
+        AutoLaunchManager::reset().unwrap();
+    }
+
+    #[test]
+    fn test_autolaunch_for_name() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let names = ["ostring_named_test_a", "ostring_named_test_b"];
+
+        AutoLaunchManager::reset().unwrap();
+        let a = AutoLaunchManager::for_name(names[0]).path("/tmp/ostring_named_test/a");
+        let b = AutoLaunchManager::for_name(names[1]).path("/tmp/ostring_named_test/b");
+        AutoLaunchManager::update_launch_many(&names, true).unwrap();
+        assert!(a.is_enabled().unwrap() && b.is_enabled().unwrap(), "批量启用后两个注册项都应启用");
+        {
+            let entries = AutoLaunchManager::lock_instance().unwrap();
+            assert_eq!(entries[names[0]].auto.get_app_name(), names[0]);
+            assert_eq!(entries[names[0]].auto.get_app_path(), "/tmp/ostring_named_test/a");
+            assert!(!entries.contains_key(DEFAULT_KEY), "具名实例不应初始化默认实例");
+        }
+
+        a.update_launch(false).unwrap();
+        assert!(!a.is_enabled().unwrap());
+        assert!(b.is_enabled().unwrap(), "禁用一个注册项不应影响其他注册项");
+
+        AutoLaunchManager::update_launch_many(&names, false).unwrap();
+        assert!(!b.is_enabled().unwrap());
+        assert!(matches!(AutoLaunchManager::for_name("").update_launch(true), Err(AutoLaunchError::EmptyName)));
+        AutoLaunchManager::reset().unwrap();
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_launch_error_admin_required() {
+        let map = |io: std::io::Error| launch_error(auto_launch::Error::Io(io));
+        let hresult = std::io::Error::from_raw_os_error(0x8007_0005_u32 as i32);
+        assert!(matches!(map(hresult), AutoLaunchError::AdminRequired), "HRESULT形式的拒绝访问应识别为需要管理员权限");
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        assert!(matches!(map(denied), AutoLaunchError::AdminRequired));
+        let missing = std::io::Error::from_raw_os_error(0x8007_0002_u32 as i32);
+        assert!(matches!(map(missing), AutoLaunchError::Backend(_)), "其他错误应保持为后端错误");
+    }
+
+    #[test]
+    fn test_autolaunch_error_into_anyhow() {
+        fn lock() -> anyhow::Result<()> {
+            Err(AutoLaunchError::LockTimeout)?
+        }
+
+        let err = lock().unwrap_err();
+        assert!(matches!(err.downcast_ref::<AutoLaunchError>(), Some(AutoLaunchError::LockTimeout)));
+        assert_eq!(err.to_string(), "获取锁超时");
+    }
+
+    #[test]
+    fn test_lock_with_timeout() {
+        let mutex = std::sync::Arc::new(Mutex::new(1));
+
+        let held = mutex.lock().unwrap();
+        assert!(matches!(
+            lock_with_timeout(&mutex, Duration::from_millis(50)),
+            Err(AutoLaunchError::LockTimeout)
+        ));
+        drop(held);
+
+        let poisoner = std::sync::Arc::clone(&mutex);
+        let _ = thread::spawn(move || {
+            let mut guard = poisoner.lock().unwrap();
+            *guard = 2;
+            panic!("poison");
+        })
+        .join();
+        assert!(mutex.is_poisoned());
+
+        let guard = lock_with_timeout(&mutex, Duration::from_millis(50)).unwrap();
+        assert_eq!(*guard, 2, "中毒后应取出内部值");
+        drop(guard);
+        assert!(!mutex.is_poisoned(), "中毒标记应被清除");
+    }
+
+    #[test]
+    fn test_bundle_path() {
+        let test_paths = [
+            ("/Applications/MyApp.app/Contents/MacOS/binary", Some("/Applications/MyApp.app")),
+            ("/usr/local/bin/myapp", None),
             (
                 "/Applications/MyApp.app/Contents/Frameworks/Helper.app/Contents/MacOS/binary",
-                "/Applications/MyApp.app/Contents/Frameworks/Helper.app"
+                Some("/Applications/MyApp.app/Contents/Frameworks/Helper.app"),
             ),
+            ("/binary", None),
         ];
-
         for (input, expected) in test_paths {
-            let result = (|| -> Option<String> {
-                let path = std::path::PathBuf::from(input);
-                let mut current = path.as_path();
-                while let Some(parent) = current.parent() {
-                    if let Some(extension) = current.extension() {
-                        if extension == "app" {
-                            return current.to_str().map(|s| s.to_string());
-                        }
-                    }
-                    current = parent;
-                }
-                None
-            })()
-            .unwrap_or(input.to_string());
+            assert_eq!(app_bundle_of(Path::new(input)), expected.map(PathBuf::from), "{input}");
+        }
 
-            assert_eq!(result, expected);
+        if !cfg!(target_os = "macos") {
+            assert!(AutoLaunchManager::bundle_path().is_none());
         }
     }
 }
\ No newline at end of file
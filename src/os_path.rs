@@ -1,4 +1,9 @@
+use std::io::Write;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 use anyhow::{anyhow, Result};
 use dirs;
 
@@ -11,7 +16,22 @@ pub enum PathType {
     File,
 }
 
+/// 文件独占锁，drop时自动释放
+#[derive(Debug)]
+pub struct FileLock {
+    file: std::fs::File,
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
 /// 路径管理，简化文件路径操作并支持链式调用
+///
+/// Windows上超过260字符（MAX_PATH）的绝对路径不需要额外处理，标准库的文件系统调用会自动加上
+/// `\\?\` 扩展长度前缀，因此这里保存和使用的都是原始路径
 #[derive(Debug, Clone, PartialEq)]
 pub struct PathManager {
     path: PathBuf,
@@ -25,6 +45,44 @@ impl PathManager {
         Ok(Self::dir(path))
     }
 
+    /// 从应用配置目录创建路径
+    pub fn get_config_dir() -> Result<Self> {
+        let path = dirs::config_dir().ok_or(anyhow!("无法获取配置目录"))?;
+        Ok(Self::dir(path))
+    }
+
+    /// 从应用缓存目录创建路径
+    pub fn get_cache_dir() -> Result<Self> {
+        let path = dirs::cache_dir().ok_or(anyhow!("无法获取缓存目录"))?;
+        Ok(Self::dir(path))
+    }
+
+    /// 从用户主目录创建路径
+    pub fn get_home_dir() -> Result<Self> {
+        let path = dirs::home_dir().ok_or(anyhow!("无法获取用户主目录"))?;
+        Ok(Self::dir(path))
+    }
+
+    /// 从用户下载目录创建路径
+    pub fn get_download_dir() -> Result<Self> {
+        let path = dirs::download_dir().ok_or(anyhow!("无法获取下载目录"))?;
+        Ok(Self::dir(path))
+    }
+
+    /// 从环境变量指定的路径创建目录，如 `XDG_DATA_HOME`，只构造路径，不会创建目录
+    pub fn from_env(var: &str) -> Result<Self> {
+        Self::from_env_value(var, std::env::var_os(var))
+    }
+
+    /// 根据读取到的环境变量值创建目录，`var` 只用于错误信息
+    fn from_env_value(var: &str, value: Option<std::ffi::OsString>) -> Result<Self> {
+        match value {
+            Some(value) if !value.is_empty() => Ok(Self::dir(value)),
+            Some(_) => Err(anyhow!("环境变量 '{}' 为空", var)),
+            None => Err(anyhow!("环境变量 '{}' 未设置", var)),
+        }
+    }
+
     /// 从任意路径创建目录
     pub fn dir<P: AsRef<Path>>(path: P) -> Self {
         Self { 
@@ -63,6 +121,35 @@ impl PathManager {
         Ok(self)
     }
 
+    /// 返回父目录，结果总是目录类型，路径没有父目录（如根目录或不含目录的相对文件名）时返回错误
+    pub fn parent(self) -> Result<Self> {
+        match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => Ok(Self::dir(parent)),
+            _ => Err(anyhow!("路径 '{}' 没有父目录", self.path.to_string_lossy())),
+        }
+    }
+
+    /// 规范化路径（解析 `.`、`..` 和符号链接），保留路径类型，路径不存在时返回错误
+    pub fn canonicalize(mut self) -> Result<Self> {
+        self.path = std::fs::canonicalize(&self.path)
+            .map_err(|e| anyhow!("规范化路径 '{}' 失败: {}", self.path.to_string_lossy(), e))?;
+        Ok(self)
+    }
+
+    /// 获取相对于base的路径，如果当前路径不在base下则返回None
+    pub fn relative_to(&self, base: &Path) -> Option<PathBuf> {
+        self.path.strip_prefix(base).ok().map(Path::to_path_buf)
+    }
+
+    /// 替换文件扩展名，例如将 `config.json` 变为 `config.bak`
+    ///
+    /// 如果当前路径是目录则返回错误
+    pub fn with_extension<S: AsRef<str>>(mut self, ext: S) -> Result<Self> {
+        self.check_file()?;
+        self.path.set_extension(ext.as_ref());
+        Ok(self)
+    }
+
     /// 确保路径存在，根据路径类型自动创建目录或文件
     pub fn ensure(self) -> Result<Self> {
         match self.path_type {
@@ -71,22 +158,66 @@ impl PathManager {
         }
     }
 
+    /// 确保文件存在，不存在时创建文件（包括父目录）并写入默认内容，已存在的文件保持不变
+    ///
+    /// 用于首次运行时生成默认配置文件，如果当前路径是目录则返回错误
+    pub fn ensure_with_default(self, default: &str) -> Result<Self> {
+        self.check_file()?;
+        if self.path.exists() {
+            return self.ensure_file();
+        }
+
+        self.ensure_parent()?;
+        // create_new保证并发创建时不会覆盖其他进程已写入的内容
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&self.path) {
+            Ok(mut file) => file
+                .write_all(default.as_bytes())
+                .map_err(|e| anyhow!("写入文件 '{}' 失败: {}", self.path.to_string_lossy(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(anyhow!("创建文件 '{}' 失败: {}", self.path.to_string_lossy(), e)),
+        }
+        Ok(self)
+    }
+
+    /// 将文件的访问和修改时间更新为当前时间，文件不存在时创建空文件（包括父目录）
+    ///
+    /// 不会修改文件内容，适合作为缓存失效的标记文件，如果当前路径是目录则返回错误
+    pub fn touch(&self) -> Result<()> {
+        self.check_file()?;
+        if !self.path.exists() {
+            return self.clone().ensure_file().map(|_| ());
+        }
+
+        let now = SystemTime::now();
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&self.path)
+            .and_then(|file| file.set_times(std::fs::FileTimes::new().set_accessed(now).set_modified(now)))
+            .map_err(|e| anyhow!("更新文件 '{}' 的时间失败: {}", self.path.to_string_lossy(), e))
+    }
+
     /// 确保目录存在，如果不存在则创建
+    ///
+    /// 会先检查目录（不存在时为最近的已存在上级目录）是否可写，见 [`ensure_writable`]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(path = %self.path.display())))]
     fn ensure_dir(self) -> Result<Self> {
-        if self.path.exists() {
+        if self.path.exists() && !self.path.is_dir() {
             // 如果路径存在，确保它是一个目录
-            if !self.path.is_dir() {
-                return Err(anyhow!("路径 '{}' 已存在但不是目录", 
-                    self.path.to_string_lossy()));
-            }
-        } else {
-            // 路径不存在，创建目录
-            std::fs::create_dir_all(&self.path)?;
+            return Err(anyhow!("路径 '{}' 已存在但不是目录", 
+                self.path.to_string_lossy()));
+        }
+
+        ensure_writable(&self.path)?;
+        if !self.path.exists() {
+            create_dir(&self.path)?;
+            #[cfg(feature = "tracing")]
+            tracing::debug!("已创建目录");
         }
         Ok(self)
     }
 
     /// 确保文件存在，如果不存在则创建(包括所需的父目录)
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(path = %self.path.display())))]
     fn ensure_file(self) -> Result<Self> {
         if self.path.exists() {
             // 如果路径存在，确保它是一个文件
@@ -96,46 +227,1451 @@ impl PathManager {
             }
         } else {
             // 确保父目录存在
-            if let Some(parent) = self.path.parent() {
-                if !parent.exists() {
-                    std::fs::create_dir_all(parent)?;
-                } else if !parent.is_dir() {
-                    return Err(anyhow!("父路径 '{}' 存在但不是目录", 
-                        parent.to_string_lossy()));
-                }
-            }
+            self.ensure_parent()?;
             
             // 创建文件
             std::fs::File::create(&self.path)?;
+            #[cfg(feature = "tracing")]
+            tracing::debug!("已创建文件");
         }
         
         Ok(self)
     }
+
+    /// 确保父目录存在且可写，如果不存在则创建
+    fn ensure_parent(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            if parent.exists() && !parent.is_dir() {
+                return Err(anyhow!("父路径 '{}' 存在但不是目录", 
+                    parent.to_string_lossy()));
+            }
+
+            ensure_writable(parent)?;
+            if !parent.exists() {
+                create_dir(parent)?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// 删除路径，文件类型删除文件，目录类型递归删除整个目录
+    ///
+    /// 路径不存在时直接返回成功，符号链接（包括目标不存在的链接）只删除链接本身
+    pub fn remove(self) -> Result<()> {
+        let remove_err = |e: std::io::Error| anyhow!("删除路径 '{}' 失败: {}", self.path.to_string_lossy(), e);
+        let metadata = match std::fs::symlink_metadata(&self.path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(remove_err(e)),
+        };
+
+        let result = match self.path_type {
+            // Windows上指向目录的链接需要按目录删除
+            #[cfg(windows)]
+            _ if std::os::windows::fs::FileTypeExt::is_symlink_dir(&metadata.file_type()) => {
+                std::fs::remove_dir(&self.path)
+            }
+            _ if metadata.file_type().is_symlink() => std::fs::remove_file(&self.path),
+            PathType::Directory => std::fs::remove_dir_all(&self.path),
+            PathType::File => std::fs::remove_file(&self.path),
+        };
+        result.map_err(remove_err)
+    }
+
+    /// 清空目录下的所有条目但保留目录本身，如果当前路径是文件则返回错误
+    ///
+    /// 目录不存在时直接返回成功，符号链接只删除链接本身
+    pub fn clear(&self) -> Result<()> {
+        self.check_dir()?;
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        let clear_err = |e: std::io::Error| anyhow!("清空目录 '{}' 失败: {}", self.path.to_string_lossy(), e);
+        for entry in std::fs::read_dir(&self.path).map_err(clear_err)? {
+            let entry = entry.map_err(clear_err)?;
+            let result = match entry.file_type().map_err(clear_err)?.is_dir() {
+                true => std::fs::remove_dir_all(entry.path()),
+                false => std::fs::remove_file(entry.path()),
+            };
+            result.map_err(clear_err)?;
+        }
+        Ok(())
+    }
+
+    /// 列出目录下的所有条目，按文件名排序，如果当前路径是文件则返回错误
+    ///
+    /// 每个条目根据实际类型返回目录或文件类型的PathManager
+    pub fn entries(&self) -> Result<Vec<PathManager>> {
+        self.check_dir()?;
+        let read_dir = std::fs::read_dir(&self.path)
+            .map_err(|e| anyhow!("读取目录 '{}' 失败: {}", self.path.to_string_lossy(), e))?;
+
+        let mut entries = read_dir
+            .map(|entry| {
+                let path = entry?.path();
+                Ok(match path.is_dir() {
+                    true => Self::dir(path),
+                    false => Self::file(path),
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort_by(|a, b| a.path.file_name().cmp(&b.path.file_name()));
+        Ok(entries)
+    }
+
+    /// 复制到目标路径，返回复制的字节数
+    ///
+    /// 文件直接复制内容，目录递归复制整个目录树（字节数为所有文件之和）。
+    /// 会先创建目标的父目录，源和目标的路径类型不一致时返回错误
+    pub fn copy_to(&self, dest: &PathManager) -> Result<u64> {
+        if self.path_type != dest.path_type {
+            return Err(anyhow!("无法将{:?}类型的 '{}' 复制到{:?}类型的 '{}'",
+                self.path_type, self.path.to_string_lossy(),
+                dest.path_type, dest.path.to_string_lossy()));
+        }
+
+        dest.ensure_parent()?;
+        let result = match self.path_type {
+            PathType::File => std::fs::copy(&self.path, &dest.path),
+            PathType::Directory => copy_dir_all(&self.path, &dest.path),
+        };
+        result.map_err(|e| anyhow!("复制 '{}' 到 '{}' 失败: {}",
+            self.path.to_string_lossy(), dest.path.to_string_lossy(), e))
+    }
+
+    /// 移动到 `dest`，返回指向新位置、路径类型不变的PathManager，必要时创建目标的父目录
+    ///
+    /// 跨文件系统时 `rename` 会失败（EXDEV），此时退化为先复制再删除源路径
+    pub fn rename_to(self, dest: &Path) -> Result<PathManager> {
+        let moved = PathManager { path: dest.to_path_buf(), path_type: self.path_type };
+        moved.ensure_parent()?;
+        match std::fs::rename(&self.path, dest) {
+            Ok(()) => Ok(moved),
+            Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => self.move_by_copy(moved),
+            Err(e) => Err(anyhow!("移动 '{}' 到 '{}' 失败: {}",
+                self.path.to_string_lossy(), dest.to_string_lossy(), e)),
+        }
+    }
+
+    /// 复制到目标后删除源路径，用于无法直接rename的情况
+    fn move_by_copy(self, dest: PathManager) -> Result<PathManager> {
+        self.copy_to(&dest)?;
+        self.remove()?;
+        Ok(dest)
+    }
+
+    /// 在当前路径创建指向 `target` 的符号链接，必要时创建父目录
+    ///
+    /// Windows下根据路径类型选择目录链接或文件链接（可能需要开发者模式或管理员权限）。
+    /// 当前路径已存在任何内容（包括失效的符号链接）时返回错误
+    pub fn symlink_to(&self, target: &Path) -> Result<()> {
+        if self.path.symlink_metadata().is_ok() {
+            return Err(anyhow!("路径 '{}' 已存在，无法创建符号链接", self.path.to_string_lossy()));
+        }
+
+        self.ensure_parent()?;
+        #[cfg(unix)]
+        let result = std::os::unix::fs::symlink(target, &self.path);
+        #[cfg(windows)]
+        let result = match self.path_type {
+            PathType::Directory => std::os::windows::fs::symlink_dir(target, &self.path),
+            PathType::File => std::os::windows::fs::symlink_file(target, &self.path),
+        };
+        result.map_err(|e| anyhow!("创建符号链接 '{}' -> '{}' 失败: {}",
+            self.path.to_string_lossy(), target.to_string_lossy(), e))
+    }
+
+    /// 当前路径所在文件系统的可用空间字节数（当前用户可用的部分）
+    ///
+    /// 路径尚不存在时查询最近的已存在上级目录，用于在写入大文件前确认空间是否足够
+    pub fn available_space(&self) -> Result<u64> {
+        let path = std::path::absolute(&self.path)
+            .map_err(|e| anyhow!("无法解析路径 '{}': {}", self.path.to_string_lossy(), e))?;
+        let existing = path
+            .ancestors()
+            .find(|ancestor| ancestor.exists())
+            .ok_or(anyhow!("路径 '{}' 没有已存在的上级目录", self.path.to_string_lossy()))?;
+        available_space_of(existing)
+            .map_err(|e| anyhow!("查询 '{}' 的可用空间失败: {}", existing.to_string_lossy(), e))
+    }
+
+    /// 在系统文件管理器中显示当前路径：文件会被选中，目录会被打开
+    ///
+    /// Windows使用 `explorer /select,`，macOS使用 `open -R`。Linux的 `xdg-open` 无法选中文件，
+    /// 文件改为打开其所在目录。路径不存在时返回错误
+    pub fn reveal_in_file_manager(&self) -> Result<()> {
+        if !self.path.exists() {
+            return Err(anyhow!("路径 '{}' 不存在，无法在文件管理器中显示", self.path.to_string_lossy()));
+        }
+        let path = std::path::absolute(&self.path)
+            .map_err(|e| anyhow!("无法解析路径 '{}': {}", self.path.to_string_lossy(), e))?;
+
+        let status = reveal_command(&path, path.is_dir())
+            .status()
+            .map_err(|e| anyhow!("启动文件管理器失败: {}", e))?;
+        // explorer即使成功也会返回非0退出码
+        if !cfg!(windows) && !status.success() {
+            return Err(anyhow!("文件管理器打开 '{}' 失败: {}", self.path.to_string_lossy(), status));
+        }
+        Ok(())
+    }
+
+    /// 将当前文件复制为同目录下的 `<stem>.<unix时间戳>.<ext>` 备份，返回备份文件
+    ///
+    /// 同一秒内多次备份会覆盖同一个备份文件，如果当前路径是目录则返回错误
+    pub fn backup(&self) -> Result<PathManager> {
+        self.check_file()?;
+        let (stem, ext) = self.stem_and_ext()?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let name = match ext {
+            Some(ext) => format!("{stem}.{timestamp}.{ext}"),
+            None => format!("{stem}.{timestamp}"),
+        };
+
+        let backup = Self::file(self.path.with_file_name(name));
+        self.copy_to(&backup)?;
+        Ok(backup)
+    }
+
+    /// 备份当前文件，并只保留最近的 `keep` 个备份，返回新的备份文件
+    pub fn backup_keep(&self, keep: usize) -> Result<PathManager> {
+        let backup = self.backup()?;
+        let (stem, ext) = self.stem_and_ext()?;
+        let parent = self.path.parent().ok_or(anyhow!("路径 '{}' 没有父目录", self.path.to_string_lossy()))?;
+
+        // 找出所有同名备份，按时间戳从新到旧排序
+        let mut backups = Vec::new();
+        for entry in std::fs::read_dir(parent)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+            let Some(rest) = name.strip_prefix(&format!("{stem}.")) else { continue };
+            let timestamp = match &ext {
+                Some(ext) => rest.strip_suffix(&format!(".{ext}")),
+                None => Some(rest),
+            };
+            if let Some(timestamp) = timestamp.and_then(|t| t.parse::<u64>().ok()) {
+                backups.push((timestamp, path));
+            }
+        }
+        backups.sort_by_key(|(timestamp, _)| std::cmp::Reverse(*timestamp));
+
+        for (_, path) in backups.into_iter().skip(keep) {
+            std::fs::remove_file(&path)
+                .map_err(|e| anyhow!("删除旧备份 '{}' 失败: {}", path.to_string_lossy(), e))?;
+        }
+        Ok(backup)
+    }
+
+    /// 获取文件名主干和扩展名
+    fn stem_and_ext(&self) -> Result<(String, Option<String>)> {
+        let stem = self.path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or(anyhow!("无法获取 '{}' 的文件名", self.path.to_string_lossy()))?;
+        let ext = self.path.extension().and_then(|e| e.to_str());
+        Ok((stem.to_string(), ext.map(String::from)))
+    }
+
+    /// 检查路径是否存在，不会创建任何内容
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// 获取大小（字节），不会创建任何内容
+    ///
+    /// 文件返回文件大小，目录返回目录树下所有文件大小之和（不跟随符号链接）
+    pub fn size(&self) -> Result<u64> {
+        let result = match self.path_type {
+            PathType::File => std::fs::metadata(&self.path).map(|m| m.len()),
+            PathType::Directory => dir_size(&self.path),
+        };
+        result.map_err(|e| anyhow!("获取 '{}' 的大小失败: {}", self.path.to_string_lossy(), e))
+    }
+
+    /// 读取文件内容为字符串，如果当前路径是目录则返回错误
+    pub fn read_string(&self) -> Result<String> {
+        self.check_file()?;
+        std::fs::read_to_string(&self.path)
+            .map_err(|e| anyhow!("读取文件 '{}' 失败: {}", self.path.to_string_lossy(), e))
+    }
+
+    /// 将字符串写入文件（覆盖原有内容），必要时创建父目录，如果当前路径是目录则返回错误
+    pub fn write_string<S: AsRef<str>>(&self, content: S) -> Result<()> {
+        self.write_bytes(content.as_ref().as_bytes())
+    }
+
+    /// 读取文件的全部字节，不要求内容为UTF-8，如果当前路径是目录则返回错误
+    pub fn read_bytes(&self) -> Result<Vec<u8>> {
+        self.check_file()?;
+        std::fs::read(&self.path)
+            .map_err(|e| anyhow!("读取文件 '{}' 失败: {}", self.path.to_string_lossy(), e))
+    }
+
+    /// 将字节写入文件（覆盖原有内容），必要时创建父目录，如果当前路径是目录则返回错误
+    pub fn write_bytes(&self, data: &[u8]) -> Result<()> {
+        self.check_file()?;
+        self.ensure_parent()?;
+        std::fs::write(&self.path, data)
+            .map_err(|e| anyhow!("写入文件 '{}' 失败: {}", self.path.to_string_lossy(), e))
+    }
+
+    /// 将字符串追加到文件末尾，文件不存在时创建文件及父目录，如果当前路径是目录则返回错误
+    pub fn append_string(&self, content: &str) -> Result<()> {
+        self.check_file()?;
+        self.ensure_parent()?;
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(content.as_bytes()))
+            .map_err(|e| anyhow!("追加写入文件 '{}' 失败: {}", self.path.to_string_lossy(), e))
+    }
+
+    /// 获取文件的独占建议锁，阻塞直到获取成功，返回的FileLock被drop时释放锁
+    ///
+    /// 用于协调多个进程对同一文件的写入（Unix下为 `flock`，Windows下为 `LockFileEx`）。
+    /// 文件不存在时会创建文件及父目录，如果当前路径是目录则返回错误
+    pub fn lock_exclusive(&self) -> Result<FileLock> {
+        self.check_file()?;
+        self.ensure_parent()?;
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&self.path)
+            .and_then(|file| file.lock().map(|_| file))
+            .map_err(|e| anyhow!("锁定文件 '{}' 失败: {}", self.path.to_string_lossy(), e))?;
+        Ok(FileLock { file })
+    }
+
+    /// 原子地将字符串写入文件，必要时创建父目录，如果当前路径是目录则返回错误
+    ///
+    /// 先写入同目录下的临时文件再重命名覆盖目标文件，
+    /// 读取方只会看到旧内容或新内容，不会看到写了一半的文件
+    pub fn write_string_atomic(&self, content: &str) -> Result<()> {
+        self.check_file()?;
+        self.ensure_parent()?;
+
+        let temp_path = self.temp_sibling()?;
+        let result = std::fs::write(&temp_path, content)
+            .and_then(|_| std::fs::rename(&temp_path, &self.path));
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(anyhow!("写入文件 '{}' 失败: {}", self.path.to_string_lossy(), e));
+        }
+        Ok(())
+    }
+
+    /// 写入字符串并调用 `sync_all` 等待数据落盘后再返回，必要时创建父目录，如果当前路径是目录则返回错误
+    ///
+    /// 每次写入都要等待磁盘确认，通常比 `write_string` 慢一到几个数量级，只用于断电也不能丢的数据
+    pub fn write_string_synced(&self, content: &str) -> Result<()> {
+        self.check_file()?;
+        self.ensure_parent()?;
+        write_synced(&self.path, content.as_bytes())
+            .map_err(|e| anyhow!("写入文件 '{}' 失败: {}", self.path.to_string_lossy(), e))
+    }
+
+    /// 原子且持久地写入字符串：临时文件落盘后再重命名，Unix下还会同步父目录使重命名本身落盘
+    ///
+    /// 性能开销与 `write_string_synced` 相同，崩溃或断电后文件只会是完整的旧内容或新内容
+    pub fn write_string_atomic_synced(&self, content: &str) -> Result<()> {
+        self.check_file()?;
+        self.ensure_parent()?;
+
+        let temp_path = self.temp_sibling()?;
+        let result = write_synced(&temp_path, content.as_bytes())
+            .and_then(|_| std::fs::rename(&temp_path, &self.path));
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(anyhow!("写入文件 '{}' 失败: {}", self.path.to_string_lossy(), e));
+        }
+
+        // Windows不支持打开目录同步，重命名的持久性由NTFS日志保证
+        #[cfg(unix)]
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::File::open(parent)
+                .and_then(|dir| dir.sync_all())
+                .map_err(|e| anyhow!("同步目录 '{}' 失败: {}", parent.to_string_lossy(), e))?;
+        }
+        Ok(())
+    }
+
+    /// 生成同目录下的临时文件路径，由目标文件名、进程ID、时间戳和计数器组成，避免并发写入冲突
+    fn temp_sibling(&self) -> Result<PathBuf> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let file_name = self.path
+            .file_name()
+            .ok_or(anyhow!("路径 '{}' 没有文件名", self.path.to_string_lossy()))?;
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let suffix = format!(".{}.{}.{}.tmp",
+            std::process::id(), nanos, COUNTER.fetch_add(1, Ordering::Relaxed));
+
+        let mut temp_name = std::ffi::OsString::from(".");
+        temp_name.push(file_name);
+        temp_name.push(suffix);
+        Ok(self.path.with_file_name(temp_name))
+    }
+
+    /// 检查当前路径类型是否为目录
+    fn check_dir(&self) -> Result<()> {
+        if self.path_type == PathType::File {
+            return Err(anyhow!("路径 '{}' 是文件，无法进行目录操作", 
+                self.path.to_string_lossy()));
+        }
+        Ok(())
+    }
+
+    /// 检查当前路径类型是否为文件
+    fn check_file(&self) -> Result<()> {
+        if self.path_type == PathType::Directory {
+            return Err(anyhow!("路径 '{}' 是目录，无法进行文件操作", 
+                self.path.to_string_lossy()));
+        }
+        Ok(())
+    }
     
     /// 获取PathBuf
     pub fn path(self) -> PathBuf {
         self.path
     }
+
+    /// 获取路径的引用，不消耗PathManager
+    pub fn path_ref(&self) -> &Path {
+        &self.path
+    }
+
+    /// 获取路径类型
+    pub fn path_type(&self) -> PathType {
+        self.path_type
+    }
     
-    /// 获取字符串路径
+    /// 获取字符串路径，路径包含无效UTF-8时返回错误，需要原样传给其他程序时使用
     pub fn string(self) -> Result<String> {
+        self.string_ref()
+    }
+
+    /// 获取字符串路径，不消耗PathManager
+    pub fn string_ref(&self) -> Result<String> {
         self.path.to_str()
             .map(String::from)
             .ok_or(anyhow!("无法将路径转换为字符串"))
     }
+
+    /// 获取用于显示的字符串路径，无效的UTF-8替换为 `U+FFFD`，不会返回错误
+    ///
+    /// 替换后的字符串不一定能还原为原路径，不要用它重新打开文件，需要精确路径时使用 [`PathManager::string`]
+    pub fn string_lossy(&self) -> String {
+        self.path.to_string_lossy().into_owned()
+    }
+}
+
+impl AsRef<Path> for PathManager {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// 按 `to_string_lossy` 显示路径，非UTF-8字符会被替换而不会panic
+impl std::fmt::Display for PathManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.to_string_lossy())
+    }
+}
+
+/// 应用的标准目录集合，各目录已连接应用名称，但不会自动创建
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppDirs {
+    /// 数据目录，如 `~/.local/share/<app>`
+    pub data: PathManager,
+    /// 配置目录，如 `~/.config/<app>`
+    pub config: PathManager,
+    /// 缓存目录，如 `~/.cache/<app>`
+    pub cache: PathManager,
+}
+
+impl AppDirs {
+    /// 获取应用的数据、配置和缓存目录，需要使用时再对单个目录调用 `ensure`
+    pub fn for_app(app_name: &str) -> Result<Self> {
+        Ok(Self {
+            data: PathManager::get_data_dir()?.join_dir(app_name)?,
+            config: PathManager::get_config_dir()?.join_dir(app_name)?,
+            cache: PathManager::get_cache_dir()?.join_dir(app_name)?,
+        })
+    }
+
+    /// 一次性创建所有目录
+    pub fn ensure_all(&self) -> Result<()> {
+        self.data.clone().ensure()?;
+        self.config.clone().ensure()?;
+        self.cache.clone().ensure()?;
+        Ok(())
+    }
+}
+
+/// 通过 `statvfs` 查询 `path` 所在文件系统的可用空间
+#[cfg(unix)]
+fn available_space_of(path: &Path) -> std::io::Result<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: path是以NUL结尾的C字符串，stat在调用成功后才读取
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let stat = unsafe { stat.assume_init() };
+    #[allow(clippy::unnecessary_cast)] // 各平台的字段类型不同
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+/// 通过 `GetDiskFreeSpaceExW` 查询 `path` 所在卷的可用空间
+#[cfg(windows)]
+fn available_space_of(path: &Path) -> std::io::Result<u64> {
+    use std::os::windows::ffi::OsStrExt;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetDiskFreeSpaceExW(
+            directory: *const u16,
+            free_bytes_available: *mut u64,
+            total_bytes: *mut u64,
+            total_free_bytes: *mut u64,
+        ) -> i32;
+    }
+
+    // 接受文件路径时按其所在目录查询
+    let dir = match path.is_dir() {
+        true => path,
+        false => path.parent().unwrap_or(path),
+    };
+    let wide: Vec<u16> = dir.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available = 0u64;
+    // SAFETY: wide以NUL结尾，不需要的输出参数传空指针
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, std::ptr::null_mut(), std::ptr::null_mut())
+    };
+    match ok {
+        0 => Err(std::io::Error::last_os_error()),
+        _ => Ok(available),
+    }
+}
+
+/// 生成在文件管理器中显示 `path` 的命令，`path` 应为绝对路径
+#[cfg(windows)]
+fn reveal_command(path: &Path, is_dir: bool) -> std::process::Command {
+    use std::os::windows::process::CommandExt;
+
+    let mut command = std::process::Command::new("explorer");
+    // explorer自行解析命令行，/select,后的路径需要整体加引号
+    match is_dir {
+        true => command.arg(path),
+        false => command.raw_arg(format!("/select,\"{}\"", path.display())),
+    };
+    command
+}
+
+/// 生成在文件管理器中显示 `path` 的命令，`path` 应为绝对路径
+#[cfg(target_os = "macos")]
+fn reveal_command(path: &Path, is_dir: bool) -> std::process::Command {
+    let mut command = std::process::Command::new("open");
+    if !is_dir {
+        command.arg("-R");
+    }
+    command.arg(path);
+    command
+}
+
+/// 生成在文件管理器中显示 `path` 的命令，`path` 应为绝对路径
+#[cfg(not(any(windows, target_os = "macos")))]
+fn reveal_command(path: &Path, is_dir: bool) -> std::process::Command {
+    let target = match is_dir {
+        true => path,
+        false => path.parent().unwrap_or(path),
+    };
+    let mut command = std::process::Command::new("xdg-open");
+    command.arg(target);
+    command
+}
+
+/// 已确认可写的目录，每个目录在进程内只检查一次
+static WRITABLE_DIRS: LazyLock<Mutex<HashSet<PathBuf>>> = LazyLock::new(Default::default);
+
+/// 检查 `path`（不存在时为最近的已存在上级目录）能否写入，不可写时返回带路径的错误，
+/// 而不是等到创建目录或写入文件时才报告底层的权限错误
+///
+/// 检查结果按目录缓存，进程运行期间权限发生变化不会重新检查
+fn ensure_writable(path: &Path) -> Result<()> {
+    let Some(dir) = path.ancestors().find(|p| p.is_dir()) else {
+        return Ok(());
+    };
+    if WRITABLE_DIRS.lock().unwrap_or_else(|e| e.into_inner()).contains(dir) {
+        return Ok(());
+    }
+
+    check_writable(dir).map_err(|e| anyhow!("数据目录不可写 '{}': {}", dir.to_string_lossy(), e))?;
+    WRITABLE_DIRS.lock().unwrap_or_else(|e| e.into_inner()).insert(dir.to_path_buf());
+    Ok(())
+}
+
+/// 递归创建目录，权限不足时返回带路径的错误
+fn create_dir(path: &Path) -> Result<()> {
+    std::fs::create_dir_all(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => anyhow!("数据目录不可写 '{}': {}", path.to_string_lossy(), e),
+        _ => anyhow!("创建目录 '{}' 失败: {}", path.to_string_lossy(), e),
+    })
+}
+
+/// 在目录中创建并删除一个临时文件，检查当前进程能否写入该目录
+fn check_writable(dir: &Path) -> Result<()> {
+    let probe = PathManager::file(dir.join(".write_check")).temp_sibling()?;
+    std::fs::OpenOptions::new().write(true).create_new(true).open(&probe)?;
+    std::fs::remove_file(&probe)?;
+    Ok(())
+}
+
+/// 写入文件并等待数据落盘
+fn write_synced(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(data)?;
+    file.sync_all()
+}
+
+/// 递归复制目录，返回复制的字节数
+fn copy_dir_all(src: &Path, dst: &Path) -> std::io::Result<u64> {
+    std::fs::create_dir_all(dst)?;
+    let mut total = 0;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        total += match entry.file_type()?.is_dir() {
+            true => copy_dir_all(&entry.path(), &target)?,
+            false => std::fs::copy(entry.path(), &target)?,
+        };
+    }
+    Ok(total)
+}
+
+/// 递归统计目录下所有文件的大小
+fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        total += match file_type.is_dir() {
+            true => dir_size(&entry.path())?,
+            false => entry.metadata()?.len(),
+        };
+    }
+    Ok(total)
+}
+
+/// JSON读写，需要启用 `json` 特性
+#[cfg(feature = "json")]
+impl PathManager {
+    /// 读取文件并反序列化JSON，如果当前路径是目录则返回错误
+    pub fn read_json<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        let content = self.read_string()?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("解析JSON文件 '{}' 失败: {}", self.path.to_string_lossy(), e))
+    }
+
+    /// 将数据序列化为格式化的JSON并写入文件，必要时创建父目录，如果当前路径是目录则返回错误
+    pub fn write_json<T: serde::Serialize>(&self, value: &T) -> Result<()> {
+        let content = serde_json::to_string_pretty(value)
+            .map_err(|e| anyhow!("序列化JSON失败: {}", e))?;
+        self.write_string(content)
+    }
+}
+
+/// 合并连续文件事件的时间窗口
+#[cfg(feature = "watch")]
+const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// 从第一个事件开始最多等待的时间，避免持续变化的文件使通知无限推迟
+#[cfg(feature = "watch")]
+const WATCH_MAX_DELAY: std::time::Duration = std::time::Duration::from_millis(1000);
+
+/// 文件变化监听，由 [`PathManager::watch`] 创建，需要启用 `watch` 特性
+///
+/// 可以像 `Receiver<()>` 一样接收通知，被丢弃后后台线程和底层监听在下一个去抖周期内退出
+#[cfg(feature = "watch")]
+#[derive(Debug)]
+pub struct PathWatcher {
+    rx: std::sync::mpsc::Receiver<()>,
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[cfg(feature = "watch")]
+impl std::ops::Deref for PathWatcher {
+    type Target = std::sync::mpsc::Receiver<()>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rx
+    }
+}
+
+#[cfg(feature = "watch")]
+impl Drop for PathWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// 文件变化监听，需要启用 `watch` 特性
+#[cfg(feature = "watch")]
+impl PathManager {
+    /// 监听路径变化，每次变化（经过去抖合并）向返回的 [`PathWatcher`] 发送一个 `()`
+    ///
+    /// 文件类型监听所在目录并只关注该文件，因此编辑器先写临时文件再替换的保存方式也能触发；
+    /// 目录类型在其中任意条目新增、修改或删除时触发（不递归子目录）。
+    /// 连续 200ms 内的事件会合并为一次，持续变化时最迟在第一个事件后 1s 通知
+    pub fn watch(&self) -> Result<PathWatcher> {
+        use notify::{EventKind, RecursiveMode, Watcher};
+
+        let watch_err = |e: &dyn std::fmt::Display| anyhow!("监听路径 '{}' 失败: {}", self.path.to_string_lossy(), e);
+        let (watch_dir, target) = match self.path_type {
+            PathType::Directory => (std::fs::canonicalize(&self.path).map_err(|e| watch_err(&e))?, None),
+            PathType::File => {
+                let file_name = self.path.file_name().ok_or_else(|| watch_err(&"缺少文件名"))?;
+                let parent = match self.path.parent() {
+                    Some(parent) if !parent.as_os_str().is_empty() => parent,
+                    _ => Path::new("."),
+                };
+                let parent = std::fs::canonicalize(parent).map_err(|e| watch_err(&e))?;
+                let target = parent.join(file_name);
+                (parent, Some(target))
+            }
+        };
+
+        let (raw_tx, raw_rx) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let _ = raw_tx.send(res);
+        })
+        .map_err(|e| watch_err(&e))?;
+        watcher
+            .watch(&watch_dir, RecursiveMode::NonRecursive)
+            .map_err(|e| watch_err(&e))?;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let stopped = std::sync::Arc::clone(&stop);
+        std::thread::spawn(move || {
+            use std::sync::mpsc::RecvTimeoutError;
+
+            // watcher需要与线程同生命周期
+            let _watcher = watcher;
+            let relevant = |event: &notify::Event| {
+                !matches!(event.kind, EventKind::Access(_))
+                    && target.as_ref().is_none_or(|target| event.paths.iter().any(|p| p == target))
+            };
+            // 每个去抖周期检查一次PathWatcher是否已被丢弃
+            while !stopped.load(Ordering::Relaxed) {
+                match raw_rx.recv_timeout(WATCH_DEBOUNCE) {
+                    Ok(Ok(event)) if relevant(&event) => {}
+                    Ok(_) | Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+                // 合并时间窗口内的后续事件，只有相关事件会重新开始计时，总等待不超过WATCH_MAX_DELAY
+                let start = std::time::Instant::now();
+                let mut quiet_until = start + WATCH_DEBOUNCE;
+                loop {
+                    let deadline = quiet_until.min(start + WATCH_MAX_DELAY);
+                    let wait = deadline.saturating_duration_since(std::time::Instant::now());
+                    match raw_rx.recv_timeout(wait) {
+                        Ok(Ok(event)) if relevant(&event) => {
+                            quiet_until = std::time::Instant::now() + WATCH_DEBOUNCE;
+                        }
+                        Ok(_) => {}
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+                if tx.send(()).is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(PathWatcher { rx, stop })
+    }
+}
+
+/// 文件校验，需要启用 `hash` 特性
+#[cfg(feature = "hash")]
+impl PathManager {
+    /// 分块读取文件计算SHA-256，返回小写十六进制摘要，如果当前路径是目录则返回错误
+    pub fn sha256(&self) -> Result<String> {
+        use sha2::{Digest, Sha256};
+
+        self.check_file()?;
+        let mut file = std::fs::File::open(&self.path)
+            .map_err(|e| anyhow!("打开文件 '{}' 失败: {}", self.path.to_string_lossy(), e))?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher)
+            .map_err(|e| anyhow!("读取文件 '{}' 失败: {}", self.path.to_string_lossy(), e))?;
+        Ok(hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect())
+    }
 }
 
 /// 获取应用数据文件路径，自动创建必要的目录和文件
 pub fn get_data_file_path(app_name: &str, filename: &str) -> Result<PathManager> {
-    PathManager::get_data_dir()?.join_dir(app_name)?.join_file(filename)
+    let base = PathManager::get_data_dir()?;
+    get_data_file_path_in(base.path_ref(), app_name, filename)
+}
+
+/// 在指定的基础目录下获取应用数据文件路径，用于测试或便携部署时替代系统数据目录
+pub fn get_data_file_path_in(base: &Path, app_name: &str, filename: &str) -> Result<PathManager> {
+    PathManager::dir(base).join_dir(app_name)?.join_file(filename)
+}
+
+/// 获取应用配置文件路径，与 [`get_data_file_path`] 对应，根目录为系统配置目录
+///
+/// 只构造路径，需要创建目录和文件时调用 `ensure`
+pub fn get_config_file_path(app_name: &str, filename: &str) -> Result<PathManager> {
+    PathManager::get_config_dir()?.join_dir(app_name)?.join_file(filename)
 }
 
 /// 获取应用数据子目录路径，自动创建必要的目录和文件
 pub fn get_data_child_dir_path(app_name: &str, child_dir: Option<String>) -> Result<PathManager> {
-    let path_manager = PathManager::get_data_dir()?.join_dir(app_name)?;
-    let path_manager = match child_dir {
-        Some(child_dir) => path_manager.join_dir(&child_dir)?,
-        None => path_manager
-    };
-    Ok(path_manager)
-}
\ No newline at end of file
+    get_data_child_dir_path_nested(app_name, child_dir)
+}
+
+/// 获取应用数据下的多级子目录路径，例如 `["profiles", "default"]` 对应 `app/profiles/default`
+///
+/// 依次对每一级调用 `join_dir`，需要创建目录时调用 `ensure`
+pub fn get_data_child_dir_path_nested<I, S>(app_name: &str, segments: I) -> Result<PathManager>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<Path>,
+{
+    segments
+        .into_iter()
+        .try_fold(PathManager::get_data_dir()?.join_dir(app_name)?, |path_manager, segment| {
+            path_manager.join_dir(segment)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在系统临时目录下创建一个测试专用目录，已存在时先清空
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("ostring_base_test").join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_read_string() {
+        let dir = test_dir("read_string");
+        let file = PathManager::dir(&dir).join_file("config.json").unwrap().ensure().unwrap();
+        std::fs::write(dir.join("config.json"), "{\"name\": \"ostring\"}").unwrap();
+
+        assert_eq!(file.read_string().unwrap(), "{\"name\": \"ostring\"}");
+        assert!(PathManager::dir(&dir).read_string().is_err(), "目录不能读取内容");
+    }
+
+    #[test]
+    fn test_write_string() {
+        let dir = test_dir("write_string");
+        let file = PathManager::dir(&dir).join_dir("nested").unwrap().join_file("data.txt").unwrap();
+
+        // 父目录不存在时自动创建
+        file.write_string("first").unwrap();
+        assert_eq!(file.read_string().unwrap(), "first");
+
+        // 再次写入覆盖原有内容
+        file.write_string(String::from("second")).unwrap();
+        assert_eq!(file.read_string().unwrap(), "second");
+
+        assert!(PathManager::dir(&dir).write_string("x").is_err(), "目录不能写入内容");
+    }
+
+    #[test]
+    fn test_write_string_atomic() {
+        let dir = test_dir("write_string_atomic");
+        let file = PathManager::dir(&dir).join_file("config.json").unwrap();
+
+        file.write_string_atomic("old").unwrap();
+        file.write_string_atomic("new").unwrap();
+        assert_eq!(file.read_string().unwrap(), "new");
+
+        // 不应残留临时文件
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_remove_file() {
+        let dir = test_dir("remove_file");
+        let file = PathManager::dir(&dir).join_file("data.log").unwrap().ensure().unwrap();
+
+        file.clone().remove().unwrap();
+        assert!(!dir.join("data.log").exists());
+        // 重复删除不报错
+        file.remove().unwrap();
+    }
+
+    #[test]
+    fn test_remove_dir() {
+        let dir = test_dir("remove_dir");
+        let tree = PathManager::dir(&dir).join_dir("tree").unwrap();
+        tree.clone().join_dir("a").unwrap().join_file("1.txt").unwrap().ensure().unwrap();
+        tree.clone().join_dir("b").unwrap().join_dir("c").unwrap().ensure().unwrap();
+
+        tree.clone().remove().unwrap();
+        assert!(!dir.join("tree").exists());
+        tree.remove().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_remove_symlink() {
+        let dir = test_dir("remove_symlink");
+        let dangling = dir.join("dangling.log");
+        std::os::unix::fs::symlink(dir.join("missing.log"), &dangling).unwrap();
+        PathManager::file(&dangling).remove().unwrap();
+        assert!(std::fs::symlink_metadata(&dangling).is_err(), "目标不存在的链接也应被删除");
+
+        let target = PathManager::dir(&dir).join_dir("target").unwrap().join_file("keep.txt").unwrap().ensure().unwrap();
+        let link = dir.join("link");
+        std::os::unix::fs::symlink(dir.join("target"), &link).unwrap();
+        PathManager::dir(&link).remove().unwrap();
+        assert!(std::fs::symlink_metadata(&link).is_err());
+        assert!(target.exists(), "只删除链接本身，不应删除目标目录中的内容");
+    }
+
+    #[test]
+    fn test_entries() {
+        let dir = test_dir("entries");
+        let root = PathManager::dir(&dir);
+        root.clone().join_file("b.json").unwrap().ensure().unwrap();
+        root.clone().join_dir("c").unwrap().ensure().unwrap();
+        root.clone().join_file("a.json").unwrap().ensure().unwrap();
+
+        let entries = root.entries().unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.path.file_name().unwrap().to_owned()).collect();
+        assert_eq!(names, ["a.json", "b.json", "c"]);
+        assert_eq!(entries[0].path_type(), PathType::File);
+        assert_eq!(entries[2].path_type(), PathType::Directory);
+
+        assert!(entries[0].entries().is_err(), "文件不能列出条目");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Config {
+            name: String,
+            port: u16,
+        }
+
+        let dir = test_dir("json_round_trip");
+        let file = PathManager::dir(&dir).join_dir("nested").unwrap().join_file("config.json").unwrap();
+        let config = Config { name: "ostring".into(), port: 8080 };
+
+        file.write_json(&config).unwrap();
+        assert_eq!(file.read_json::<Config>().unwrap(), config);
+
+        file.write_string("not json").unwrap();
+        let err = file.read_json::<Config>().unwrap_err();
+        assert!(err.to_string().contains("config.json"), "错误信息应包含路径");
+    }
+
+    #[test]
+    fn test_copy_to() {
+        let dir = test_dir("copy_to");
+        let root = PathManager::dir(&dir);
+
+        // 复制文件
+        let src = root.clone().join_file("data.txt").unwrap();
+        src.write_string("hello").unwrap();
+        let backup = root.clone().join_dir("backup").unwrap().join_file("data.txt").unwrap();
+        assert_eq!(src.copy_to(&backup).unwrap(), 5);
+        assert_eq!(backup.read_string().unwrap(), "hello");
+
+        // 递归复制目录
+        let tree = root.clone().join_dir("tree").unwrap();
+        tree.clone().join_dir("a").unwrap().join_file("1.txt").unwrap().write_string("abc").unwrap();
+        tree.clone().join_file("2.txt").unwrap().write_string("de").unwrap();
+        let copy = root.clone().join_dir("copy").unwrap();
+        assert_eq!(tree.copy_to(&copy).unwrap(), 5);
+        assert_eq!(std::fs::read_to_string(dir.join("copy/a/1.txt")).unwrap(), "abc");
+
+        // 类型不一致
+        assert!(src.copy_to(&copy).is_err(), "文件不能复制到目录类型");
+    }
+
+    #[test]
+    fn test_backup() {
+        let dir = test_dir("backup");
+        let config = PathManager::dir(&dir).join_file("config.json").unwrap();
+        config.write_string("v1").unwrap();
+
+        let backup = config.backup().unwrap();
+        assert_eq!(backup.read_string().unwrap(), "v1");
+        let name = backup.path.file_name().unwrap().to_str().unwrap().to_string();
+        assert!(name.starts_with("config.") && name.ends_with(".json"), "备份文件名格式错误: {name}");
+
+        // 模拟更早的备份，只保留最近一个
+        std::fs::write(dir.join("config.100.json"), "old").unwrap();
+        std::fs::write(dir.join("config.200.json"), "old").unwrap();
+        let latest = config.backup_keep(1).unwrap();
+        assert!(latest.path.exists());
+        assert!(!dir.join("config.100.json").exists());
+        assert!(!dir.join("config.200.json").exists());
+        assert!(config.path.exists(), "原文件不应被删除");
+
+        assert!(PathManager::dir(&dir).backup().is_err(), "目录不能备份");
+    }
+
+    #[test]
+    fn test_exists_and_size() {
+        let dir = test_dir("exists_and_size");
+        let root = PathManager::dir(&dir).join_dir("data").unwrap();
+        let file = root.clone().join_file("a.txt").unwrap();
+
+        // 检查不会创建路径
+        assert!(!root.exists());
+        assert!(!file.exists());
+        assert!(file.size().is_err());
+        assert!(!root.exists());
+
+        file.write_string("12345").unwrap();
+        root.clone().join_dir("sub").unwrap().join_file("b.txt").unwrap().write_string("678").unwrap();
+        assert!(file.exists());
+        assert_eq!(file.size().unwrap(), 5);
+        assert_eq!(root.size().unwrap(), 8);
+    }
+
+    #[test]
+    fn test_canonicalize_and_relative_to() {
+        let dir = test_dir("canonicalize").canonicalize().unwrap();
+        PathManager::dir(&dir).join_dir("a").unwrap().ensure().unwrap();
+        let file = PathManager::dir(&dir).join_dir("b").unwrap().join_file("c.txt").unwrap().ensure().unwrap();
+
+        let messy = PathManager::dir(&dir).join_dir("a/../b").unwrap().join_file("c.txt").unwrap();
+        let canonical = messy.canonicalize().unwrap();
+        assert_eq!(canonical, file);
+        assert_eq!(canonical.path_type, PathType::File);
+        assert_eq!(canonical.relative_to(&dir), Some(PathBuf::from("b").join("c.txt")));
+        assert_eq!(canonical.relative_to(Path::new("/not/a/prefix")), None);
+
+        assert!(PathManager::dir(dir.join("missing")).canonicalize().is_err());
+    }
+
+    #[test]
+    fn test_append_string() {
+        let dir = test_dir("append_string");
+        let log = PathManager::dir(&dir).join_dir("logs").unwrap().join_file("app.log").unwrap();
+
+        log.append_string("line1\n").unwrap();
+        log.append_string("line2\n").unwrap();
+        log.append_string("line3\n").unwrap();
+        assert_eq!(log.read_string().unwrap(), "line1\nline2\nline3\n");
+
+        assert!(PathManager::dir(&dir).append_string("x").is_err(), "目录不能追加内容");
+    }
+
+    #[test]
+    fn test_lock_exclusive() {
+        let dir = test_dir("lock_exclusive");
+        let file = PathManager::dir(&dir).join_file("shared.dat").unwrap();
+
+        let lock = file.lock_exclusive().unwrap();
+        let other = std::fs::File::open(dir.join("shared.dat")).unwrap();
+        assert!(other.try_lock().is_err(), "持有锁时其他句柄不能加锁");
+
+        drop(lock);
+        assert!(other.try_lock().is_ok(), "释放后可以重新加锁");
+    }
+
+    #[test]
+    fn test_with_extension() {
+        let file = PathManager::dir("/tmp").join_file("config.json").unwrap();
+        let backup = file.with_extension("bak").unwrap();
+        assert_eq!(backup.path_ref(), Path::new("/tmp/config.bak"));
+        assert_eq!(backup.path_type, PathType::File);
+
+        assert!(PathManager::dir("/tmp/data.d").with_extension("bak").is_err(), "目录类型应返回错误");
+    }
+
+    #[test]
+    fn test_get_data_file_path_in() {
+        let dir = test_dir("data_file_path_in");
+        let file = get_data_file_path_in(&dir, "myapp", "config.json").unwrap();
+        assert_eq!(file.path_ref(), dir.join("myapp").join("config.json"));
+
+        file.write_string("{}").unwrap();
+        assert!(dir.join("myapp").join("config.json").is_file());
+    }
+
+    #[test]
+    fn test_get_config_file_path() {
+        let file = get_config_file_path("myapp", "settings.toml").unwrap();
+        let expected = dirs::config_dir().unwrap().join("myapp").join("settings.toml");
+        assert_eq!(file.path_ref(), expected);
+        assert_eq!(file.path_type(), PathType::File);
+    }
+
+    #[test]
+    fn test_display_and_as_ref() {
+        let dir = test_dir("display_and_as_ref");
+        let file = PathManager::dir(&dir).join_file("a.txt").unwrap();
+        std::fs::write(&file, "hello").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "hello");
+        assert_eq!(file.to_string(), dir.join("a.txt").to_string_lossy());
+    }
+
+    #[test]
+    fn test_clear() {
+        let dir = test_dir("clear");
+        std::fs::create_dir_all(dir.join("sub").join("nested")).unwrap();
+        std::fs::write(dir.join("a.txt"), "a").unwrap();
+        std::fs::write(dir.join("sub").join("nested").join("b.txt"), "b").unwrap();
+
+        let manager = PathManager::dir(&dir);
+        manager.clear().unwrap();
+        assert!(dir.is_dir(), "目录本身应保留");
+        assert!(manager.entries().unwrap().is_empty(), "目录应被清空");
+
+        assert!(PathManager::dir(&dir).join_file("a.txt").unwrap().clear().is_err(), "文件不能清空");
+    }
+
+    #[test]
+    fn test_read_write_bytes() {
+        let dir = test_dir("read_write_bytes");
+        let file = PathManager::dir(&dir).join_dir("blobs").unwrap().join_file("data.bin").unwrap();
+        let data = [0u8, 0xff, 0xfe, 0x80, 0x00, 0x7f];
+
+        file.write_bytes(&data).unwrap();
+        assert_eq!(file.read_bytes().unwrap(), data);
+        assert!(file.read_string().is_err(), "非UTF-8内容不能按字符串读取");
+
+        assert!(PathManager::dir(&dir).write_bytes(&data).is_err(), "目录不能写入内容");
+        assert!(PathManager::dir(&dir).read_bytes().is_err(), "目录不能读取内容");
+    }
+
+    #[test]
+    fn test_get_data_child_dir_path_nested() {
+        let base = PathManager::get_data_dir().unwrap().path().join("myapp");
+        let nested = get_data_child_dir_path_nested("myapp", ["profiles", "default"]).unwrap();
+        assert_eq!(nested.path_ref(), base.join("profiles").join("default"));
+
+        let single = get_data_child_dir_path("myapp", Some("logs".to_string())).unwrap();
+        assert_eq!(single.path_ref(), base.join("logs"));
+        let root = get_data_child_dir_path("myapp", None).unwrap();
+        assert_eq!(root.path_ref(), base);
+    }
+
+    #[test]
+    fn test_ensure_with_default() {
+        let dir = test_dir("ensure_with_default");
+        let config = PathManager::dir(&dir).join_dir("conf").unwrap().join_file("config.json").unwrap();
+
+        let config = config.ensure_with_default("{\"debug\": false}").unwrap();
+        assert_eq!(config.read_string().unwrap(), "{\"debug\": false}");
+
+        config.write_string("{\"debug\": true}").unwrap();
+        let config = config.ensure_with_default("{\"debug\": false}").unwrap();
+        assert_eq!(config.read_string().unwrap(), "{\"debug\": true}", "已存在的文件不应被覆盖");
+
+        assert!(PathManager::dir(&dir).ensure_with_default("{}").is_err(), "目录不能写入默认内容");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_to() {
+        let dir = test_dir("symlink_to");
+        let target = PathManager::dir(&dir).join_dir("v1.2.0").unwrap().ensure().unwrap();
+        target.clone().join_file("app.bin").unwrap().write_string("v1").unwrap();
+
+        let link = PathManager::dir(&dir).join_dir("current").unwrap();
+        link.symlink_to(target.path_ref()).unwrap();
+        assert_eq!(std::fs::read_link(&link).unwrap(), target.path_ref());
+        assert_eq!(link.clone().join_file("app.bin").unwrap().read_string().unwrap(), "v1");
+
+        assert!(link.symlink_to(target.path_ref()).is_err(), "已存在的路径不能再创建链接");
+    }
+
+    #[test]
+    fn test_rename_to() {
+        let dir = test_dir("rename_to");
+        let tmp = PathManager::dir(&dir).join_file("report.tmp").unwrap();
+        tmp.write_string("done").unwrap();
+
+        let dest = dir.join("out").join("report.txt");
+        let moved = tmp.rename_to(&dest).unwrap();
+        assert_eq!(moved.path_ref(), dest);
+        assert_eq!(moved.path_type(), PathType::File);
+        assert_eq!(moved.read_string().unwrap(), "done");
+        assert!(!dir.join("report.tmp").exists());
+
+        // 跨文件系统时的复制+删除回退
+        let src = PathManager::dir(&dir).join_dir("src").unwrap();
+        src.clone().join_file("a.txt").unwrap().write_string("a").unwrap();
+        let copied = src.move_by_copy(PathManager::dir(dir.join("dst"))).unwrap();
+        assert_eq!(copied.join_file("a.txt").unwrap().read_string().unwrap(), "a");
+        assert!(!dir.join("src").exists());
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn test_sha256() {
+        let dir = test_dir("sha256");
+        let file = PathManager::dir(&dir).join_file("data.txt").unwrap();
+        file.write_string("abc").unwrap();
+
+        assert_eq!(
+            file.sha256().unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(PathManager::dir(&dir).sha256().is_err(), "目录不能计算摘要");
+    }
+
+    #[test]
+    fn test_app_dirs_for_app() {
+        let dirs = AppDirs::for_app("myapp").unwrap();
+        assert_eq!(dirs.data.path_ref(), PathManager::get_data_dir().unwrap().path().join("myapp"));
+        assert_eq!(dirs.config.path_ref(), PathManager::get_config_dir().unwrap().path().join("myapp"));
+        assert_eq!(dirs.cache.path_ref(), PathManager::get_cache_dir().unwrap().path().join("myapp"));
+        assert_eq!(dirs.cache.path_type(), PathType::Directory);
+    }
+
+    #[cfg(feature = "watch")]
+    #[test]
+    fn test_watch_file() {
+        let dir = test_dir("watch_file");
+        let config = PathManager::dir(&dir).join_file("config.json").unwrap();
+        config.write_string("{}").unwrap();
+
+        let events = config.watch().unwrap();
+        PathManager::dir(&dir).join_file("other.txt").unwrap().write_string("x").unwrap();
+        assert!(
+            events.recv_timeout(std::time::Duration::from_millis(500)).is_err(),
+            "其他文件的变化不应触发"
+        );
+
+        for i in 0..5 {
+            config.write_string(format!("{{\"v\": {i}}}")).unwrap();
+        }
+        assert!(events.recv_timeout(std::time::Duration::from_secs(5)).is_ok(), "修改文件应触发事件");
+
+        // 同目录下频繁写入的其他文件不应推迟通知
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let writer = {
+            let stop = std::sync::Arc::clone(&stop);
+            let log = dir.join("app.log");
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let _ = std::fs::write(&log, "line");
+                    std::thread::sleep(std::time::Duration::from_millis(20));
+                }
+            })
+        };
+        std::thread::sleep(WATCH_DEBOUNCE);
+        let _ = events.try_iter().count();
+        config.write_string("{\"v\": 9}").unwrap();
+        let notified = events.recv_timeout(WATCH_MAX_DELAY * 3);
+        stop.store(true, Ordering::Relaxed);
+        writer.join().unwrap();
+        assert!(notified.is_ok(), "其他文件持续变化时仍应按时通知");
+
+        // 后台线程退出时释放其持有的stop
+        let stop = std::sync::Arc::clone(&events.stop);
+        drop(events);
+        let deadline = std::time::Instant::now() + WATCH_MAX_DELAY;
+        while std::sync::Arc::strong_count(&stop) > 1 && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(std::sync::Arc::strong_count(&stop), 1, "PathWatcher丢弃后后台线程应退出");
+    }
+
+    #[test]
+    fn test_write_string_synced() {
+        let dir = test_dir("write_string_synced");
+        let file = PathManager::dir(&dir).join_dir("db").unwrap().join_file("state.json").unwrap();
+
+        file.write_string_synced("{\"v\": 1}").unwrap();
+        assert_eq!(file.read_string().unwrap(), "{\"v\": 1}");
+
+        file.write_string_atomic_synced("{\"v\": 2}").unwrap();
+        assert_eq!(file.read_string().unwrap(), "{\"v\": 2}");
+        assert_eq!(PathManager::dir(dir.join("db")).entries().unwrap().len(), 1, "不应残留临时文件");
+
+        assert!(PathManager::dir(&dir).write_string_synced("x").is_err(), "目录不能写入内容");
+        assert!(PathManager::dir(&dir).write_string_atomic_synced("x").is_err(), "目录不能写入内容");
+    }
+
+    #[test]
+    fn test_parent() {
+        let dir = test_dir("parent");
+        let file = PathManager::dir(&dir).join_dir("logs").unwrap().join_file("app.log").unwrap();
+
+        let logs = file.parent().unwrap();
+        assert_eq!(logs.path_ref(), dir.join("logs"));
+        assert_eq!(logs.path_type(), PathType::Directory);
+        assert_eq!(logs.parent().unwrap().path_ref(), dir);
+
+        let root = if cfg!(windows) { "C:\\" } else { "/" };
+        assert!(PathManager::dir(root).parent().is_err(), "根目录没有父目录");
+        assert!(PathManager::file("app.log").parent().is_err(), "相对文件名没有父目录");
+    }
+
+    #[test]
+    fn test_from_env() {
+        let dir = test_dir("from_env").join("data");
+        let manager = PathManager::from_env_value("MYAPP_DATA", Some(dir.clone().into_os_string())).unwrap();
+        assert_eq!(manager.path_ref(), dir);
+        assert_eq!(manager.path_type(), PathType::Directory);
+        assert!(!dir.exists(), "调用ensure之前不应创建目录");
+        manager.ensure().unwrap();
+        assert!(dir.is_dir());
+
+        let empty = PathManager::from_env_value("MYAPP_DATA", Some(Default::default())).unwrap_err();
+        assert!(empty.to_string().contains("为空"), "{empty}");
+        let unset = PathManager::from_env_value("MYAPP_DATA", None).unwrap_err();
+        assert!(unset.to_string().contains("未设置"), "{unset}");
+
+        // cargo运行测试时总会设置该变量，不需要修改进程环境
+        let manifest = PathManager::from_env("CARGO_MANIFEST_DIR").unwrap();
+        assert_eq!(manifest.path_ref(), Path::new(env!("CARGO_MANIFEST_DIR")));
+        assert!(PathManager::from_env("OSTRING_BASE_TEST_FROM_ENV_UNSET").is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_long_path() {
+        // 超过MAX_PATH的绝对路径，由标准库自动加上扩展长度前缀
+        let dir = test_dir("long_path");
+        let deep = (0..30).fold(dir.clone(), |path, i| path.join(format!("nested_directory_{i:02}")));
+        assert!(deep.is_absolute() && deep.as_os_str().len() > 260);
+
+        let file = PathManager::dir(&deep).join_file("config.json").unwrap().ensure().unwrap();
+        assert_eq!(file.path_ref(), deep.join("config.json"));
+        assert!(deep.join("config.json").is_file());
+
+        file.write_string("{}").unwrap();
+        assert_eq!(file.read_string().unwrap(), "{}");
+        file.touch().unwrap();
+        file.remove().unwrap();
+        assert!(!deep.join("config.json").exists());
+    }
+
+    #[test]
+    fn test_check_writable() {
+        let dir = test_dir("check_writable");
+        check_writable(&dir).unwrap();
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0, "检查后应删除临时文件");
+
+        let file = dir.join("not_a_dir");
+        std::fs::write(&file, "").unwrap();
+        assert!(check_writable(&file).is_err(), "文件路径下无法创建临时文件");
+        assert!(check_writable(&dir.join("missing")).is_err());
+
+        PathManager::dir(&dir).join_dir("app").unwrap().join_dir("logs").unwrap().ensure().unwrap();
+        assert!(dir.join("app").join("logs").is_dir());
+        let names: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(names.len(), 2, "创建目录前的检查不应留下临时文件: {names:?}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ensure_readonly_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // root不受目录权限限制
+        if unsafe { libc::geteuid() } == 0 {
+            return;
+        }
+        let dir = test_dir("readonly_dir");
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        let file = PathManager::dir(&dir).join_file("config.json").unwrap();
+        let err = file.write_string("{}").unwrap_err().to_string();
+        assert!(err.contains("数据目录不可写") && err.contains("readonly_dir"), "{err}");
+        let err = PathManager::dir(&dir).ensure().unwrap_err().to_string();
+        assert!(err.contains("数据目录不可写"), "已存在的目录不可写时也应报错: {err}");
+        assert!(PathManager::dir(dir.join("logs")).ensure().is_err());
+
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_touch() {
+        let dir = test_dir("touch");
+        let file = PathManager::dir(&dir).join_dir("cache").unwrap().join_file("stamp").unwrap();
+        file.touch().unwrap();
+        assert!(dir.join("cache").join("stamp").is_file(), "不存在时应创建文件");
+
+        file.write_string("keep").unwrap();
+        let old = SystemTime::now() - std::time::Duration::from_secs(3600);
+        std::fs::File::options().write(true).open(&file).unwrap().set_modified(old).unwrap();
+
+        file.touch().unwrap();
+        let modified = std::fs::metadata(&file).unwrap().modified().unwrap();
+        assert!(modified > old + std::time::Duration::from_secs(1800), "修改时间应更新为当前时间");
+        assert_eq!(file.read_string().unwrap(), "keep", "不应修改文件内容");
+        assert!(PathManager::dir(&dir).touch().is_err(), "目录类型应返回错误");
+    }
+
+    #[test]
+    fn test_reveal_in_file_manager() {
+        let dir = test_dir("reveal_in_file_manager");
+        let missing = PathManager::dir(&dir).join_file("missing.txt").unwrap();
+        let err = missing.reveal_in_file_manager().unwrap_err();
+        assert!(err.to_string().contains("不存在"), "{err}");
+
+        // 只检查生成的命令，不真正启动文件管理器
+        let file = dir.join("a.txt");
+        let command = reveal_command(&file, false);
+        let args: Vec<_> = command.get_args().collect();
+        if cfg!(target_os = "macos") {
+            assert_eq!(command.get_program(), "open");
+            assert_eq!(args, ["-R".as_ref(), file.as_os_str()]);
+        } else if cfg!(windows) {
+            assert_eq!(command.get_program(), "explorer");
+        } else {
+            assert_eq!(command.get_program(), "xdg-open");
+            assert_eq!(args, [dir.as_os_str()], "Linux下应打开文件所在目录");
+            assert_eq!(reveal_command(&dir, true).get_args().collect::<Vec<_>>(), [dir.as_os_str()]);
+        }
+    }
+
+    #[test]
+    fn test_available_space() {
+        let dir = test_dir("available_space");
+        let existing = PathManager::dir(&dir).available_space().unwrap();
+        assert!(existing > 0, "临时目录所在文件系统应有可用空间");
+
+        // 尚不存在的路径按最近的已存在上级目录查询，与该目录位于同一文件系统
+        let missing = PathManager::dir(&dir).join_dir("a").unwrap().join_file("b.bin").unwrap();
+        let space = missing.available_space().unwrap();
+        assert!(space > 0);
+        assert!(!dir.join("a").exists(), "查询不应创建目录");
+    }
+
+    #[test]
+    fn test_string_lossy() {
+        let file = PathManager::dir("/tmp/data").join_file("config.json").unwrap();
+        assert_eq!(file.string_lossy(), file.string_ref().unwrap());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+
+            let invalid = PathManager::file(std::ffi::OsStr::from_bytes(b"/tmp/bad\xff.txt"));
+            assert!(invalid.string_ref().is_err(), "无效UTF-8应返回错误");
+            assert_eq!(invalid.string_lossy(), "/tmp/bad\u{FFFD}.txt");
+        }
+    }
+}
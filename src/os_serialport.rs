@@ -1,29 +1,509 @@
-use serde::Serialize;
-use serialport::{self, SerialPortType};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serialport::{self, SerialPort, SerialPortInfo, SerialPortType, UsbPortInfo};
+pub use serialport::{DataBits, FlowControl, Parity, StopBits};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
-#[derive(Serialize, Debug)]
+/// 打开串口时默认的读写超时
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PortInfo {
     id: usize,
     label: String,
     desc: String,
+    /// USB厂商ID
+    vid: u16,
+    /// USB产品ID
+    pid: u16,
+    /// USB序列号，设备未提供时为None
+    serial_number: Option<String>,
+    /// USB产品名称，设备未提供时为None
+    product: Option<String>,
+    /// 串口类型：USB、PCI、Bluetooth或Unknown
+    port_type: String,
+    /// 重新扫描后保持不变的标识，可用于记住用户选择的设备
+    ///
+    /// USB串口为 `vid:pid:序列号`，没有序列号时用串口名称代替序列号；其他串口为串口名称。
+    /// 只保证尽量稳定，不保证全局唯一（如多个设备序列号相同）
+    stable_id: String,
+}
+
+impl PortInfo {
+    fn from_usb(id: usize, port_name: &str, info: &UsbPortInfo) -> Self {
+        PortInfo {
+            id,
+            label: port_name.to_string(),
+            desc: friendly_name(info)
+                .or_else(|| info.manufacturer.clone())
+                .unwrap_or("unknown".to_string()),
+            vid: info.vid,
+            pid: info.pid,
+            serial_number: info.serial_number.clone(),
+            product: info.product.clone(),
+            port_type: "USB".to_string(),
+            stable_id: format!(
+                "{:04x}:{:04x}:{}",
+                info.vid,
+                info.pid,
+                info.serial_number.as_deref().unwrap_or(port_name)
+            ),
+        }
+    }
+
+    fn from_port(id: usize, port: &SerialPortInfo) -> Self {
+        let port_type = match &port.port_type {
+            SerialPortType::UsbPort(info) => return Self::from_usb(id, &port.port_name, info),
+            SerialPortType::PciPort => "PCI",
+            SerialPortType::BluetoothPort => "Bluetooth",
+            SerialPortType::Unknown => "Unknown",
+        };
+        PortInfo {
+            id,
+            label: port.port_name.clone(),
+            desc: port_type.to_string(),
+            vid: 0,
+            pid: 0,
+            serial_number: None,
+            product: None,
+            port_type: port_type.to_string(),
+            stable_id: port.port_name.clone(),
+        }
+    }
 }
 
+/// Windows下的设备友好名称，如 "Silicon Labs CP210x USB to UART Bridge (COM7)"
+///
+/// serialport在Windows上通过SetupAPI将SPDRP_FRIENDLYNAME读入 `product`
+#[cfg(windows)]
+fn friendly_name(info: &UsbPortInfo) -> Option<String> {
+    info.product.clone().filter(|name| !name.is_empty())
+}
+
+#[cfg(not(windows))]
+fn friendly_name(_info: &UsbPortInfo) -> Option<String> {
+    None
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
 pub fn serial_port_list() -> Vec<PortInfo> {
-    let mut result: Vec<PortInfo> = vec![];
-    if let Ok(ports) = serialport::available_ports() {
-        result.extend(ports.iter().enumerate().filter_map(|(current_id, p)| {
-            if let SerialPortType::UsbPort(info) = &p.port_type {
-                Some(PortInfo {
-                    id: current_id,
-                    label: p.port_name.clone(),
-                    desc: info.clone().manufacturer.unwrap_or("unknown".to_string()),
-                })
-            } else {
-                None
+    let result: Vec<PortInfo> = serial_port_iter().collect();
+    #[cfg(feature = "tracing")]
+    tracing::debug!(usb = result.len(), "USB串口筛选完成");
+    result
+}
+
+/// 逐个生成USB串口信息，可以配合 `take`、`filter` 使用而不必构建完整列表
+///
+/// 系统枚举本身仍是一次性完成的，只有PortInfo的构建是惰性的
+pub fn serial_port_iter() -> impl Iterator<Item = PortInfo> {
+    let ports = serialport::available_ports();
+    #[cfg(feature = "tracing")]
+    match &ports {
+        Ok(ports) => tracing::debug!(found = ports.len(), "串口枚举完成"),
+        Err(e) => tracing::warn!(error = %e, "串口枚举失败"),
+    }
+    ports.unwrap_or_default().into_iter().enumerate().filter_map(|(current_id, p)| {
+        if let SerialPortType::UsbPort(info) = &p.port_type {
+            Some(PortInfo::from_usb(current_id, &p.port_name, info))
+        } else {
+            None
+        }
+    })
+}
+
+/// 将当前的USB串口列表以JSON写入文件，用于在崩溃报告等场景中记录设备信息
+///
+/// 需要同时启用 `path` 和 `json` 特性，`path` 为目录类型时返回错误
+#[cfg(all(feature = "path", feature = "json"))]
+pub fn log_serial_ports_to(path: &crate::os_path::PathManager) -> Result<()> {
+    path.write_json(&serial_port_list())
+}
+
+/// 按USB厂商ID和产品ID筛选USB串口，None表示不限制
+pub fn serial_port_list_filtered(vid: Option<u16>, pid: Option<u16>) -> Vec<PortInfo> {
+    serial_port_list()
+        .into_iter()
+        .filter(|port| vid.is_none_or(|vid| port.vid == vid) && pid.is_none_or(|pid| port.pid == pid))
+        .collect()
+}
+
+/// 列出所有串口，包括板载UART、蓝牙和PCI串口
+///
+/// 非USB串口的 `desc` 为串口类型名称，`vid`/`pid` 为0
+pub fn serial_port_list_all() -> Vec<PortInfo> {
+    serialport::available_ports()
+        .map(|ports| ports.iter().enumerate().map(|(id, p)| PortInfo::from_port(id, p)).collect())
+        .unwrap_or_default()
+}
+
+/// 串口热插拔事件
+#[derive(Serialize, Debug, Clone)]
+pub enum SerialPortEvent {
+    /// 新出现的串口
+    Added(PortInfo),
+    /// 被移除的串口名称
+    Removed(String),
+}
+
+/// 串口变化监听，由 [`watch_serial_ports`] 创建
+///
+/// 可以像 `Receiver<SerialPortEvent>` 一样接收事件，被丢弃后后台线程在当前轮询间隔结束时退出
+#[derive(Debug)]
+pub struct SerialPortWatcher {
+    rx: Receiver<SerialPortEvent>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Deref for SerialPortWatcher {
+    type Target = Receiver<SerialPortEvent>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rx
+    }
+}
+
+impl Drop for SerialPortWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// 在后台线程中按 `interval` 轮询USB串口列表，将与上一次扫描的差异作为事件发送
+///
+/// 首次扫描时已存在的串口会作为 `Added` 事件发送
+pub fn watch_serial_ports(interval: Duration) -> SerialPortWatcher {
+    let (tx, rx) = mpsc::channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let stopped = stop.clone();
+    thread::spawn(move || {
+        let mut previous: Vec<PortInfo> = vec![];
+        while !stopped.load(Ordering::Relaxed) {
+            let current = serial_port_list();
+            for event in diff_ports(&previous, &current) {
+                if tx.send(event).is_err() {
+                    return;
+                }
             }
-        }));
+            previous = current;
+            thread::sleep(interval);
+        }
+    });
+    SerialPortWatcher { rx, stop }
+}
+
+/// 按串口名称比较两次扫描结果
+fn diff_ports(previous: &[PortInfo], current: &[PortInfo]) -> Vec<SerialPortEvent> {
+    let removed = previous
+        .iter()
+        .filter(|old| !current.iter().any(|port| port.label == old.label))
+        .map(|old| SerialPortEvent::Removed(old.label.clone()));
+    let added = current
+        .iter()
+        .filter(|port| !previous.iter().any(|old| old.label == port.label))
+        .map(|port| SerialPortEvent::Added(port.clone()));
+    removed.chain(added).collect()
+}
+
+/// 串口参数
+///
+/// 默认为115200波特率、8N1、无流控、1秒读写超时
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerialConfig {
+    pub baud: u32,
+    pub data_bits: DataBits,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+    pub flow_control: FlowControl,
+    pub timeout: Duration,
+}
+
+impl Default for SerialConfig {
+    fn default() -> Self {
+        Self {
+            baud: 115200,
+            data_bits: DataBits::Eight,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+            flow_control: FlowControl::None,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+/// 常用的标准波特率，从低到高排列
+const STANDARD_BAUD_RATES: &[u32] = &[
+    110, 300, 600, 1200, 2400, 4800, 9600, 14400, 19200, 38400, 57600, 115200, 230400, 460800,
+    921600,
+];
+
+/// 常用的标准波特率列表，可用于填充界面下拉框
+pub fn serial_standard_baud_rates() -> &'static [u32] {
+    STANDARD_BAUD_RATES
+}
+
+/// 判断波特率是否为 [`serial_standard_baud_rates`] 中的标准值
+///
+/// 非标准波特率不一定无法使用，部分设备支持自定义波特率，可在打开前提示用户确认
+pub fn is_standard_baud(rate: u32) -> bool {
+    STANDARD_BAUD_RATES.binary_search(&rate).is_ok()
+}
+
+/// 以指定波特率打开串口，其余参数使用 [`SerialConfig::default`]
+pub fn open_port(name: &str, baud: u32) -> Result<Box<dyn SerialPort>> {
+    open_port_with(name, &SerialConfig { baud, ..Default::default() })
+}
+
+/// 按指定参数打开串口
+pub fn open_port_with(name: &str, config: &SerialConfig) -> Result<Box<dyn SerialPort>> {
+    serialport::new(name, config.baud)
+        .data_bits(config.data_bits)
+        .parity(config.parity)
+        .stop_bits(config.stop_bits)
+        .flow_control(config.flow_control)
+        .timeout(config.timeout)
+        .open()
+        .map_err(|e| open_error(name, e))
+}
+
+/// 将打开串口的错误转换为带串口名称的描述
+fn open_error(name: &str, e: serialport::Error) -> anyhow::Error {
+    match e.kind() {
+        serialport::ErrorKind::Io(io::ErrorKind::NotFound) => anyhow!("串口 '{}' 不存在", name),
+        serialport::ErrorKind::Io(io::ErrorKind::PermissionDenied) => anyhow!("没有权限访问串口 '{}'", name),
+        // Windows下串口被占用和不存在都报告为NoDevice
+        serialport::ErrorKind::NoDevice => anyhow!("串口 '{}' 不存在或已被其他程序占用", name),
+        _ => anyhow!("打开串口 '{}' 失败: {}", name, e),
+    }
+}
+
+/// 按行读写文本协议的串口封装
+///
+/// 读取超时时已收到的半行数据会保留，下一次 `read_line` 会接着拼接
+pub struct OsSerialReader {
+    reader: BufReader<Box<dyn SerialPort>>,
+    pending: Vec<u8>,
+}
+
+impl OsSerialReader {
+    /// 包装已打开的串口
+    pub fn new(port: Box<dyn SerialPort>) -> Self {
+        Self {
+            reader: BufReader::new(port),
+            pending: Vec::new(),
+        }
+    }
+
+    /// 使用 [`open_port`] 的默认配置打开串口并包装
+    pub fn open(name: &str, baud: u32) -> Result<Self> {
+        Ok(Self::new(open_port(name, baud)?))
+    }
+
+    /// 读取一行文本，去掉结尾的 `\r\n` 或 `\n`
+    ///
+    /// 超时返回的错误可以用 [`is_timeout`] 判断，调用方可以直接重试
+    pub fn read_line(&mut self) -> Result<String> {
+        let name = self.port_name();
+        read_line_from(&mut self.reader, &mut self.pending, &name)
+    }
+
+    /// 写入一行文本，自动追加 `\n`
+    pub fn write_line(&mut self, line: &str) -> Result<()> {
+        let name = self.port_name();
+        let port = self.reader.get_mut();
+        port.write_all(line.as_bytes())
+            .and_then(|_| port.write_all(b"\n"))
+            .and_then(|_| port.flush())
+            .map_err(|e| anyhow!("写入串口 '{}' 失败: {}", name, e))
+    }
+
+    /// 取回内部的串口，缓冲区中未读取的数据会被丢弃
+    pub fn into_inner(self) -> Box<dyn SerialPort> {
+        self.reader.into_inner()
+    }
+
+    fn port_name(&self) -> String {
+        self.reader.get_ref().name().unwrap_or_default()
+    }
+}
+
+/// 持有已打开串口的连接，离开作用域时自动关闭
+///
+/// 关闭后读写会返回错误，而不是继续操作已释放的句柄
+pub struct OsSerialConnection {
+    port: Option<Box<dyn SerialPort>>,
+    name: String,
+}
+
+impl OsSerialConnection {
+    /// 包装已打开的串口
+    pub fn new(port: Box<dyn SerialPort>) -> Self {
+        let name = port.name().unwrap_or_default();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(port = %name, "串口已打开");
+        Self { port: Some(port), name }
+    }
+
+    /// 使用 [`open_port`] 的默认配置打开串口
+    pub fn open(name: &str, baud: u32) -> Result<Self> {
+        Self::open_with(name, &SerialConfig { baud, ..Default::default() })
+    }
+
+    /// 按指定参数打开串口
+    pub fn open_with(name: &str, config: &SerialConfig) -> Result<Self> {
+        let mut connection = Self::new(open_port_with(name, config)?);
+        connection.name = name.to_string();
+        Ok(connection)
+    }
+
+    /// 读取数据到 `buf`，返回读取的字节数
+    ///
+    /// 超时返回的错误可以用 [`is_timeout`] 判断
+    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let name = self.name.clone();
+        self.port_mut()?.read(buf).map_err(|e| read_error(&name, e))
+    }
+
+    /// 读取数据直到连续 `idle` 时间没有新数据，或已收到 `max` 字节，用于没有分隔符的协议分帧
+    ///
+    /// 第一个字节按串口配置的超时等待，期间没有任何数据时返回超时错误（可用 [`is_timeout`] 判断）；
+    /// 收到数据后改用 `idle` 作为字节间隔超时，读取结束后恢复原超时设置。
+    /// 其他读取错误会直接返回，已收到的数据被丢弃
+    pub fn read_until_idle(&mut self, idle: Duration, max: usize) -> Result<Vec<u8>> {
+        let name = self.name.clone();
+        let port = self.port_mut()?;
+        let timeout = port.timeout();
+        let set_timeout = |port: &mut Box<dyn SerialPort>, timeout| {
+            port.set_timeout(timeout)
+                .map_err(|e| anyhow!("设置串口 '{}' 超时失败: {}", name, e))
+        };
+
+        let mut data = Vec::new();
+        let mut buf = [0u8; 256];
+        let result = loop {
+            if data.len() >= max {
+                break Ok(());
+            }
+            let want = (max - data.len()).min(buf.len());
+            match port.read(&mut buf[..want]) {
+                Ok(0) => break Ok(()),
+                Ok(n) => {
+                    let first = data.is_empty();
+                    data.extend_from_slice(&buf[..n]);
+                    if first && let Err(e) = set_timeout(port, idle) {
+                        break Err(e);
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::TimedOut && !data.is_empty() => break Ok(()),
+                Err(e) => break Err(read_error(&name, e)),
+            }
+        };
+
+        let restored = set_timeout(port, timeout);
+        result.and(restored).map(|_| data)
+    }
+
+    /// 写入全部数据并刷新
+    pub fn write(&mut self, data: &[u8]) -> Result<()> {
+        let name = self.name.clone();
+        let port = self.port_mut()?;
+        port.write_all(data)
+            .and_then(|_| port.flush())
+            .map_err(|e| anyhow!("写入串口 '{}' 失败: {}", name, e))
+    }
+
+    /// 阻塞直到发送缓冲区中的数据全部发出，用于确认命令已离开本机后再等待响应
+    pub fn flush_output(&mut self) -> Result<()> {
+        let name = self.name.clone();
+        self.port_mut()?
+            .flush()
+            .map_err(|e| anyhow!("等待串口 '{}' 发送完成失败: {}", name, e))
+    }
+
+    /// 丢弃已收到但尚未读取的数据，用于发送新请求前清除过期的响应
+    pub fn clear_input(&mut self) -> Result<()> {
+        let name = self.name.clone();
+        self.port_mut()?
+            .clear(serialport::ClearBuffer::Input)
+            .map_err(|e| anyhow!("清空串口 '{}' 接收缓冲区失败: {}", name, e))
+    }
+
+    /// 输入缓冲区中可以立即读取的字节数
+    pub fn bytes_available(&self) -> Result<u32> {
+        let port = self.port.as_ref().ok_or_else(|| self.closed_error())?;
+        port.bytes_to_read()
+            .map_err(|e| anyhow!("读取串口 '{}' 缓冲区状态失败: {}", self.name, e))
+    }
+
+    /// 串口是否仍处于打开状态
+    pub fn is_open(&self) -> bool {
+        self.port.is_some()
+    }
+
+    /// 串口名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 立即关闭串口，重复调用无影响
+    pub fn close(&mut self) {
+        if self.port.take().is_some() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(port = %self.name, "串口已关闭");
+        }
+    }
+
+    fn port_mut(&mut self) -> Result<&mut Box<dyn SerialPort>> {
+        let err = self.closed_error();
+        self.port.as_mut().ok_or(err)
+    }
+
+    fn closed_error(&self) -> anyhow::Error {
+        anyhow!("串口 '{}' 已关闭", self.name)
+    }
+}
+
+impl Drop for OsSerialConnection {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// 将读取错误转换为带串口名称的描述，超时错误保留原始 `io::Error` 供 [`is_timeout`] 判断
+fn read_error(name: &str, e: io::Error) -> anyhow::Error {
+    match e.kind() {
+        io::ErrorKind::TimedOut => anyhow::Error::new(e).context(format!("读取串口 '{}' 超时", name)),
+        _ => anyhow!("读取串口 '{}' 失败: {}", name, e),
+    }
+}
+
+/// 判断错误是否为串口读写超时
+pub fn is_timeout(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<io::Error>()
+        .is_some_and(|e| e.kind() == io::ErrorKind::TimedOut)
+}
+
+fn read_line_from<R: BufRead>(reader: &mut R, pending: &mut Vec<u8>, name: &str) -> Result<String> {
+    match reader.read_until(b'\n', pending) {
+        Ok(0) if pending.is_empty() => Err(anyhow!("串口 '{}' 已关闭", name)),
+        Ok(_) => {
+            let mut line = std::mem::take(pending);
+            while matches!(line.last(), Some(b'\n' | b'\r')) {
+                line.pop();
+            }
+            Ok(String::from_utf8_lossy(&line).into_owned())
+        }
+        Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+            Err(anyhow::Error::new(e).context(format!("读取串口 '{}' 超时", name)))
+        }
+        Err(e) => Err(anyhow!("读取串口 '{}' 失败: {}", name, e)),
     }
-    result
 }
 
 #[cfg(test)]
@@ -38,4 +518,254 @@ mod tests {
         // 或者简单地验证类型
         let _: Vec<PortInfo> = ports;
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_port_info_usb_fields() {
+        let usb = UsbPortInfo {
+            vid: 0x10c4,
+            pid: 0xea60,
+            serial_number: Some("0001".to_string()),
+            manufacturer: Some("Silicon Labs".to_string()),
+            product: Some("CP2102 USB to UART Bridge Controller".to_string()),
+        };
+        let info = PortInfo::from_usb(0, "/dev/ttyUSB0", &usb);
+        let json = serde_json::to_string(&info).unwrap();
+        let parsed: PortInfo = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.label, "/dev/ttyUSB0");
+        if cfg!(windows) {
+            assert_eq!(parsed.desc, "CP2102 USB to UART Bridge Controller");
+        } else {
+            assert_eq!(parsed.desc, "Silicon Labs");
+        }
+        assert_eq!(parsed.vid, 0x10c4);
+        assert_eq!(parsed.pid, 0xea60);
+        assert_eq!(parsed.serial_number.as_deref(), Some("0001"));
+        assert_eq!(parsed.product.as_deref(), Some("CP2102 USB to UART Bridge Controller"));
+        assert_eq!(parsed.port_type, "USB");
+        assert_eq!(parsed.stable_id, "10c4:ea60:0001");
+
+        let usb = UsbPortInfo { serial_number: None, ..usb };
+        let info = PortInfo::from_usb(5, "/dev/ttyUSB1", &usb);
+        assert_eq!(info.stable_id, "10c4:ea60:/dev/ttyUSB1");
+    }
+
+    #[test]
+    fn test_port_info_non_usb() {
+        let port = SerialPortInfo {
+            port_name: "/dev/ttyS0".to_string(),
+            port_type: SerialPortType::PciPort,
+        };
+        let info = PortInfo::from_port(3, &port);
+        assert_eq!(info.port_type, "PCI");
+        assert_eq!(info.desc, "PCI");
+        assert_eq!(info.vid, 0);
+        assert_eq!(info.stable_id, "/dev/ttyS0");
+
+        // 完整列表应包含所有USB串口
+        assert!(serial_port_list_all().len() >= serial_port_list().len());
+    }
+
+    #[test]
+    fn test_serial_port_list_filtered() {
+        // 0x0000 是保留的厂商ID，不会匹配任何真实设备
+        assert!(serial_port_list_filtered(Some(0x0000), None).is_empty());
+        assert!(serial_port_list_filtered(Some(0x0000), Some(0x0000)).is_empty());
+        assert_eq!(serial_port_list_filtered(None, None).len(), serial_port_list().len());
+    }
+
+    #[test]
+    fn test_open_port_missing() {
+        let name = if cfg!(windows) { "COM250" } else { "/dev/ostring_base_missing" };
+        let Err(err) = open_port(name, 115200) else {
+            panic!("不存在的串口应打开失败");
+        };
+        assert!(err.to_string().contains(name), "错误信息应包含串口名称: {}", err);
+    }
+
+    /// 先返回部分数据，再返回超时错误的模拟串口
+    struct TimeoutReader {
+        data: io::Cursor<Vec<u8>>,
+    }
+
+    impl io::Read for TimeoutReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.data.read(buf)? {
+                0 => Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")),
+                n => Ok(n),
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_line() {
+        let mut reader = BufReader::new(TimeoutReader {
+            data: io::Cursor::new(b"OK\r\nVALUE=1\npart".to_vec()),
+        });
+        let mut pending = Vec::new();
+        assert_eq!(read_line_from(&mut reader, &mut pending, "mock").unwrap(), "OK");
+        assert_eq!(read_line_from(&mut reader, &mut pending, "mock").unwrap(), "VALUE=1");
+
+        let err = read_line_from(&mut reader, &mut pending, "mock").unwrap_err();
+        assert!(is_timeout(&err), "超时应可被识别: {}", err);
+        assert_eq!(pending, b"part", "超时前收到的半行数据应保留");
+    }
+
+    #[test]
+    fn test_serial_config_default() {
+        let config = SerialConfig::default();
+        assert_eq!(config.data_bits, DataBits::Eight);
+        assert_eq!(config.parity, Parity::None);
+        assert_eq!(config.stop_bits, StopBits::One);
+        assert_eq!(config.timeout, Duration::from_secs(1));
+
+        let config = SerialConfig {
+            baud: 9600,
+            parity: Parity::Even,
+            stop_bits: StopBits::Two,
+            ..Default::default()
+        };
+        let Err(err) = open_port_with("/dev/ostring_base_missing", &config) else {
+            panic!("不存在的串口应打开失败");
+        };
+        assert!(err.to_string().contains("ostring_base_missing"));
+    }
+
+    #[test]
+    fn test_diff_ports() {
+        let port = |name: &str| PortInfo::from_port(0, &SerialPortInfo {
+            port_name: name.to_string(),
+            port_type: SerialPortType::Unknown,
+        });
+        let previous = vec![port("COM1"), port("COM2")];
+        let current = vec![port("COM2"), port("COM3")];
+
+        let events = diff_ports(&previous, &current);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], SerialPortEvent::Removed(name) if name == "COM1"));
+        assert!(matches!(&events[1], SerialPortEvent::Added(info) if info.label == "COM3"));
+        assert!(diff_ports(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_watch_serial_ports() {
+        let watcher = watch_serial_ports(Duration::from_millis(10));
+        let initial = serial_port_list().len();
+        let added = watcher.iter().take(initial).filter(|e| matches!(e, SerialPortEvent::Added(_))).count();
+        assert_eq!(added, initial, "首次扫描时已存在的串口应作为Added事件发送");
+
+        // 后台线程退出时释放其持有的stop
+        let stop = watcher.stop.clone();
+        drop(watcher);
+        let deadline = std::time::Instant::now() + Duration::from_secs(2);
+        while Arc::strong_count(&stop) > 1 && std::time::Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(Arc::strong_count(&stop), 1, "接收端丢弃后后台线程应退出");
+    }
+
+    #[test]
+    fn test_serial_port_iter() {
+        let labels: Vec<String> = serial_port_iter().map(|port| port.label).collect();
+        let expected: Vec<String> = serial_port_list().into_iter().map(|port| port.label).collect();
+        assert_eq!(labels, expected);
+        assert!(serial_port_iter().take(1).count() <= 1);
+    }
+
+    #[cfg(all(feature = "path", feature = "json"))]
+    #[test]
+    fn test_log_serial_ports_to() {
+        use crate::os_path::PathManager;
+
+        let dir = std::env::temp_dir().join("ostring_base_test").join("log_serial_ports_to");
+        let _ = std::fs::remove_dir_all(&dir);
+        let file = PathManager::dir(&dir).join_file("ports.json").unwrap();
+
+        log_serial_ports_to(&file).unwrap();
+        let ports: Vec<PortInfo> = file.read_json().unwrap();
+        assert_eq!(ports.len(), serial_port_list().len());
+
+        assert!(log_serial_ports_to(&PathManager::dir(&dir)).is_err(), "目录不能写入串口列表");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_serial_connection() {
+        let (master, slave) = serialport::TTYPort::pair().unwrap();
+        let mut host = OsSerialConnection::new(Box::new(master));
+        let mut device = OsSerialConnection::new(Box::new(slave));
+        assert!(host.is_open());
+
+        host.write(b"ping").unwrap();
+        let mut buf = [0u8; 4];
+        let mut read = 0;
+        while read < buf.len() {
+            read += device.read(&mut buf[read..]).unwrap();
+        }
+        assert_eq!(&buf, b"ping");
+        assert_eq!(device.bytes_available().unwrap(), 0);
+
+        host.close();
+        assert!(!host.is_open());
+        assert!(host.write(b"x").is_err(), "关闭后写入应失败");
+        assert!(host.bytes_available().is_err(), "关闭后不能查询缓冲区");
+        host.close();
+
+        assert!(OsSerialConnection::open("/dev/ostring-base-missing", 9600).is_err());
+    }
+
+    #[test]
+    fn test_standard_baud_rates() {
+        let rates = serial_standard_baud_rates();
+        assert!(rates.windows(2).all(|pair| pair[0] < pair[1]), "应从低到高排列");
+        assert!(rates.contains(&SerialConfig::default().baud), "默认波特率应为标准值");
+        assert!(is_standard_baud(9600));
+        assert!(is_standard_baud(115200));
+        assert!(!is_standard_baud(12345));
+        assert!(!is_standard_baud(0));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_until_idle() {
+        let (master, slave) = serialport::TTYPort::pair().unwrap();
+        let mut host = OsSerialConnection::new(Box::new(master));
+        let mut device = OsSerialConnection::new(Box::new(slave));
+        let idle = Duration::from_millis(50);
+
+        host.write(b"frame-1").unwrap();
+        assert_eq!(device.read_until_idle(idle, 64).unwrap(), b"frame-1");
+
+        host.write(b"0123456789").unwrap();
+        assert_eq!(device.read_until_idle(idle, 4).unwrap(), b"0123", "达到max时应立即返回");
+        assert_eq!(device.read_until_idle(idle, 64).unwrap(), b"456789");
+
+        let err = device.read_until_idle(idle, 64).unwrap_err();
+        assert!(is_timeout(&err), "没有任何数据时应返回超时错误: {}", err);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_flush_output_and_clear_input() {
+        let (master, slave) = serialport::TTYPort::pair().unwrap();
+        let mut host = OsSerialConnection::new(Box::new(master));
+        let mut device = OsSerialConnection::new(Box::new(slave));
+
+        host.write(b"stale response").unwrap();
+        host.flush_output().unwrap();
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        while device.bytes_available().unwrap() == 0 && std::time::Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(device.bytes_available().unwrap() > 0, "刷新后对端应能收到数据");
+
+        device.clear_input().unwrap();
+        assert_eq!(device.bytes_available().unwrap(), 0, "清空后不应有待读取的数据");
+        let err = device.read_until_idle(Duration::from_millis(20), 64).unwrap_err();
+        assert!(is_timeout(&err), "清空后应读不到旧数据: {}", err);
+
+        host.close();
+        assert!(host.flush_output().is_err());
+        assert!(host.clear_input().is_err());
+    }
+}
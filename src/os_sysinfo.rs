@@ -1,52 +1,1001 @@
 use serde::{Deserialize, Serialize};
-use sysinfo::{Networks, System};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+use sysinfo::{
+    Components, CpuRefreshKind, Disks, Networks, ProcessRefreshKind, ProcessesToUpdate, System,
+    MINIMUM_CPU_UPDATE_INTERVAL,
+};
 
-const GB_IN_BYTES: f64 = 1_073_741_824.0;
+/// 容量显示单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemoryUnit {
+    MB,
+    #[default]
+    GB,
+    TB,
+    /// 自动选择使数值不小于1的最大单位
+    Auto,
+}
+
+/// 容量单位表：(单位, 名称, 字节数)，从大到小排列
+const UNITS: [(MemoryUnit, &str, f64); 3] = [
+    (MemoryUnit::TB, "TB", 1_099_511_627_776.0),
+    (MemoryUnit::GB, "GB", 1_073_741_824.0),
+    (MemoryUnit::MB, "MB", 1_048_576.0),
+];
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
 pub struct OsSysInfo {
     name: String,
     version: String,
     host: String,
-    memory: String,
+    /// 内核版本，无法获取时为空
+    kernel_version: String,
+    /// CPU架构，如 `x86_64`、`aarch64`
+    arch: String,
+    /// 总内存字节数
+    memory_total: u64,
+    /// 已用内存字节数
+    memory_used: u64,
+    /// 可用内存字节数（包括可回收的缓存）
+    memory_available: u64,
+    /// 交换空间总字节数，没有交换空间时为0
+    swap_total: u64,
+    /// 已用交换空间字节数
+    swap_used: u64,
     networks: Vec<OsNet>,
+    disks: Vec<OsDisk>,
+    cpu: OsCpu,
+    /// 全局CPU使用率（百分比）
+    ///
+    /// 使用率需要两次刷新之间的差值才能计算，只有 [`OsSysSampler`] 第二次及之后的刷新才会填充，
+    /// 一次性采集和采样器的首次刷新均为None
+    cpu_usage: Option<f32>,
+    /// `memory_display` 使用的显示单位，不参与序列化
+    #[serde(skip)]
+    unit: MemoryUnit,
+    /// 1、5、15分钟平均负载，随CPU部分一起采集
+    ///
+    /// 仅Linux/macOS等Unix系统支持，Windows下始终为None
+    load_average: Option<[f64; 3]>,
+    /// 温度传感器读数，平台不提供传感器时为空
+    temperatures: Vec<OsTemp>,
+    /// 电池状态，没有电池或未选择采集时为None，需要启用 `battery` 特性
+    #[cfg(feature = "battery")]
+    battery: Option<OsBattery>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
-struct OsNet {
+#[serde(rename_all = "camelCase", default)]
+pub struct OsNet {
     id: usize,
     name: String,
+    /// MAC地址，统一为大写冒号分隔格式，如 `52:54:00:12:34:56`
     mac: String,
+    /// 按MAC地址前缀（OUI）查到的厂商名称，不在内置表中时为None，需要启用 `oui` 特性
+    #[cfg(feature = "oui")]
+    vendor: Option<String>,
+    /// 接口上的IPv4地址，格式为 `addr/prefix`，没有地址时为空
+    ipv4: Vec<String>,
+    /// 接口上的IPv6地址，格式为 `addr/prefix`，没有地址时为空
+    ipv6: Vec<String>,
+    /// 累计接收字节数
+    total_received: u64,
+    /// 累计发送字节数
+    total_transmitted: u64,
+}
+
+impl OsNet {
+    /// 接口名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// MAC地址，大写冒号分隔格式
+    pub fn mac(&self) -> &str {
+        &self.mac
+    }
+
+    /// MAC地址对应的厂商名称，不在内置表中时为None
+    #[cfg(feature = "oui")]
+    pub fn vendor(&self) -> Option<&str> {
+        self.vendor.as_deref()
+    }
+
+    /// IPv4地址列表，格式为 `addr/prefix`
+    pub fn ipv4(&self) -> &[String] {
+        &self.ipv4
+    }
+
+    /// IPv6地址列表，格式为 `addr/prefix`
+    pub fn ipv6(&self) -> &[String] {
+        &self.ipv6
+    }
+
+    /// 累计接收字节数
+    pub fn total_received(&self) -> u64 {
+        self.total_received
+    }
+
+    /// 累计发送字节数
+    pub fn total_transmitted(&self) -> u64 {
+        self.total_transmitted
+    }
+
+    /// 接口上所有可解析的地址（不含前缀长度）
+    fn addrs(&self) -> impl Iterator<Item = IpAddr> + '_ {
+        self.ipv4
+            .iter()
+            .chain(&self.ipv6)
+            .filter_map(|ip| ip.split('/').next()?.parse().ok())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OsDisk {
+    name: String,
+    mount_point: String,
+    total_space: String,
+    available_space: String,
+    /// 总容量字节数
+    total_bytes: u64,
+    /// 可用空间字节数
+    available_bytes: u64,
+}
+
+impl OsDisk {
+    /// 磁盘使用率（百分比），限制在 [0, 100]，总容量为0（如未插入介质的可移动驱动器）时返回0.0
+    pub fn usage_percent(&self) -> f64 {
+        usage_percent(self.total_bytes.saturating_sub(self.available_bytes), self.total_bytes)
+    }
+}
+
+/// CPU静态信息
+///
+/// 目前只包含型号、核心数和频率，不包含使用率（使用率需要间隔两次刷新才准确）
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OsCpu {
+    brand: String,
+    physical_core_count: usize,
+    logical_core_count: usize,
+    frequency_mhz: u64,
+}
+
+/// 温度传感器读数，单位为摄氏度
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OsTemp {
+    label: String,
+    temperature: f32,
+    /// 临界温度，传感器未提供时为None
+    critical: Option<f32>,
+}
+
+/// 电池状态
+#[cfg(feature = "battery")]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OsBattery {
+    /// 电量百分比（0-100）
+    percentage: f32,
+    /// 充电状态：charging、discharging、full、empty或unknown
+    state: String,
+    /// 预计充满所需秒数，未在充电或无法估计时为None
+    time_to_full_secs: Option<u64>,
+}
+
+/// 进程信息
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OsProcess {
+    pid: u32,
+    name: String,
+    /// CPU使用率（百分比），多核时可能超过100
+    cpu_usage: f32,
+    memory_bytes: u64,
+}
+
+impl OsProcess {
+    /// 进程ID
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// 进程名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// CPU使用率（百分比），多核时可能超过100
+    pub fn cpu_usage(&self) -> f32 {
+        self.cpu_usage
+    }
+
+    /// 占用内存字节数
+    pub fn memory_bytes(&self) -> u64 {
+        self.memory_bytes
+    }
+}
+
+/// 进程列表的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessSort {
+    /// 按进程ID升序
+    #[default]
+    Pid,
+    /// 按进程名称升序
+    Name,
+    /// 按CPU使用率降序
+    Cpu,
+    /// 按内存占用降序
+    Memory,
+}
+
+/// 不代表真实存储设备的伪文件系统
+const PSEUDO_FILE_SYSTEMS: &[&str] = &[
+    "tmpfs", "devtmpfs", "ramfs", "overlay", "squashfs", "proc", "sysfs", "devfs", "autofs",
+];
+
+/// 视为虚拟接口的名称前缀：回环、Docker网桥、veth对、Linux网桥和VPN隧道
+const VIRTUAL_INTERFACE_PREFIXES: &[&str] = &["lo", "docker", "veth", "br-", "tun"];
+
+/// 判断网络接口是否为回环或虚拟接口
+///
+/// 启发式规则：MAC地址全为0，或名称以 [`VIRTUAL_INTERFACE_PREFIXES`] 中任一前缀开头
+fn is_virtual_interface(name: &str, mac: &str) -> bool {
+    let zero_mac = mac.chars().filter(|c| *c != ':' && *c != '-').all(|c| c == '0');
+    zero_mac || VIRTUAL_INTERFACE_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// 将MAC地址统一为大写冒号分隔格式，接受 `:`、`-` 或 `.` 分隔以及不分隔的写法
+///
+/// 去掉分隔符后不是12位十六进制数时只转为大写，不改变格式
+fn normalize_mac(mac: &str) -> String {
+    let digits: String = mac.chars().filter(|c| !matches!(c, ':' | '-' | '.')).collect();
+    if digits.len() != 12 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return mac.to_ascii_uppercase();
+    }
+    digits
+        .to_ascii_uppercase()
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| String::from_utf8_lossy(pair).into_owned())
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// 常见网卡厂商的OUI前缀表，按前缀排序以便二分查找
+///
+/// 只收录常见的虚拟化平台和硬件厂商，不是完整的IEEE注册表
+#[cfg(feature = "oui")]
+const OUI_VENDORS: &[(&str, &str)] = &[
+    ("00:03:93", "Apple"),
+    ("00:03:FF", "Microsoft"),
+    ("00:04:4B", "NVIDIA"),
+    ("00:05:69", "VMware"),
+    ("00:0A:95", "Apple"),
+    ("00:0C:29", "VMware"),
+    ("00:0D:3A", "Microsoft"),
+    ("00:15:5D", "Microsoft"),
+    ("00:16:3E", "Xen"),
+    ("00:17:88", "Philips Lighting"),
+    ("00:1A:11", "Google"),
+    ("00:1B:63", "Apple"),
+    ("00:1C:42", "Parallels"),
+    ("00:1C:B3", "Apple"),
+    ("00:25:90", "Super Micro Computer"),
+    ("00:50:56", "VMware"),
+    ("00:E0:4C", "Realtek"),
+    ("08:00:27", "Oracle VirtualBox"),
+    ("18:B4:30", "Nest Labs"),
+    ("24:0A:C4", "Espressif"),
+    ("30:AE:A4", "Espressif"),
+    ("52:54:00", "QEMU"),
+    ("B8:27:EB", "Raspberry Pi"),
+    ("DC:A6:32", "Raspberry Pi"),
+    ("E4:5F:01", "Raspberry Pi"),
+];
+
+/// 按MAC地址的前3个字节查询厂商名称
+#[cfg(feature = "oui")]
+fn oui_vendor(mac: &str) -> Option<String> {
+    let mac = normalize_mac(mac);
+    let prefix = mac.get(..8)?;
+    OUI_VENDORS
+        .binary_search_by(|(oui, _)| (*oui).cmp(prefix))
+        .ok()
+        .map(|index| OUI_VENDORS[index].1.to_string())
+}
+
+/// 读取平均负载，Windows不支持时返回None
+#[cfg(not(windows))]
+fn load_average() -> Option<[f64; 3]> {
+    let load = System::load_average();
+    Some([load.one, load.five, load.fifteen])
+}
+
+#[cfg(windows)]
+fn load_average() -> Option<[f64; 3]> {
+    None
+}
+
+/// IPv6链路本地地址需要指定scope id才能绑定，连通性检查时跳过
+fn is_link_local(addr: &IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(_) => false,
+        IpAddr::V6(addr) => (addr.segments()[0] & 0xffc0) == 0xfe80,
+    }
+}
+
+/// 默认路由使用的本机地址
+///
+/// 对公网地址"连接"一个UDP套接字只会查询路由表，不会发出数据包。离线或没有默认路由时返回None
+fn default_route_addr() -> Option<IpAddr> {
+    ["0.0.0.0:0", "[::]:0"]
+        .into_iter()
+        .zip(["8.8.8.8:53", "[2001:4860:4860::8888]:53"])
+        .find_map(|(bind, target)| {
+            let socket = UdpSocket::bind(bind).ok()?;
+            socket.connect(target).ok()?;
+            Some(socket.local_addr().ok()?.ip())
+        })
+        .filter(|addr| !addr.is_unspecified())
+}
+
+/// 选出主网络接口：优先选地址与 `route_addr` 相同的接口，
+/// 否则在拥有非回环、非链路本地地址的接口中选累计流量最大的
+fn pick_primary_interface(networks: &[OsNet], route_addr: Option<IpAddr>) -> Option<OsNet> {
+    if let Some(route_addr) = route_addr
+        && let Some(net) = networks.iter().find(|net| net.addrs().any(|addr| addr == route_addr))
+    {
+        return Some(net.clone());
+    }
+    networks
+        .iter()
+        .filter(|net| net.addrs().any(|addr| !addr.is_loopback() && !is_link_local(&addr)))
+        .max_by_key(|net| net.total_received + net.total_transmitted)
+        .cloned()
+}
+
+/// 将接口名、传感器名、挂载点等转换为指标名的一段，空白、`.` 和路径分隔符替换为 `_`，避免与层级分隔符混淆
+fn metric_segment(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_whitespace() || matches!(c, '.' | '/' | '\\' | ':') { '_' } else { c })
+        .collect()
+}
+
+/// 以 `source` 为源地址尝试TCP连接 `target`
+fn tcp_reachable(source: IpAddr, target: SocketAddr, timeout: Duration) -> bool {
+    use socket2::{Domain, Socket, Type};
+
+    let Ok(socket) = Socket::new(Domain::for_address(target), Type::STREAM, None) else {
+        return false;
+    };
+    socket.bind(&SocketAddr::new(source, 0).into()).is_ok()
+        && socket.connect_timeout(&target.into(), timeout).is_ok()
+}
+
+/// 计算使用率（百分比）并限制在 [0, 100]，总量为0时返回0.0
+fn usage_percent(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (used as f64 / total as f64 * 100.0).clamp(0.0, 100.0)
+}
+
+/// 将字节数格式化为 "x.xx 单位"
+///
+/// `unit` 为Auto时根据 `reference` 选择单位，使成对显示的数值（如已用/总量）单位一致
+fn format_size(bytes: u64, unit: MemoryUnit, reference: u64) -> String {
+    let (_, name, size) = UNITS
+        .iter()
+        .find(|(u, _, size)| match unit {
+            MemoryUnit::Auto => reference as f64 >= *size,
+            unit => *u == unit,
+        })
+        .unwrap_or(&UNITS[UNITS.len() - 1]);
+    format!("{:.2} {}", bytes as f64 / size, name)
+}
+
+/// 格式化为 "已用 / 总量"
+fn format_usage(used: u64, total: u64, unit: MemoryUnit) -> String {
+    format!("{} / {}", format_size(used, unit, total), format_size(total, unit, total))
+}
+
+/// 按需采集系统信息的构建器，只刷新选中的部分，未选中部分保持默认值
+///
+/// 系统名称、版本、主机名、内核版本和CPU架构开销很小，总是会填充
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsSysInfoBuilder {
+    memory: bool,
+    cpu: bool,
+    networks: bool,
+    disks: bool,
+    temperatures: bool,
+    #[cfg(feature = "battery")]
+    battery: bool,
+    physical_only: bool,
+    unit: MemoryUnit,
+}
+
+impl OsSysInfoBuilder {
+    /// 创建不采集任何可选部分的构建器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建采集所有部分的构建器
+    pub fn all() -> Self {
+        Self {
+            memory: true,
+            cpu: true,
+            networks: true,
+            disks: true,
+            temperatures: true,
+            #[cfg(feature = "battery")]
+            battery: false,
+            physical_only: false,
+            unit: MemoryUnit::GB,
+        }
+    }
+
+    /// 是否采集内存和交换空间
+    pub fn memory(mut self, enable: bool) -> Self {
+        self.memory = enable;
+        self
+    }
+
+    /// 是否采集CPU信息
+    pub fn cpu(mut self, enable: bool) -> Self {
+        self.cpu = enable;
+        self
+    }
+
+    /// 是否采集网络接口
+    pub fn networks(mut self, enable: bool) -> Self {
+        self.networks = enable;
+        self
+    }
+
+    /// 是否采集磁盘信息
+    pub fn disks(mut self, enable: bool) -> Self {
+        self.disks = enable;
+        self
+    }
+
+    /// 是否采集温度传感器读数
+    pub fn temperatures(mut self, enable: bool) -> Self {
+        self.temperatures = enable;
+        self
+    }
+
+    /// 是否采集电池状态，[`OsSysInfoBuilder::all`] 默认不采集
+    #[cfg(feature = "battery")]
+    pub fn battery(mut self, enable: bool) -> Self {
+        self.battery = enable;
+        self
+    }
+
+    /// 是否只保留物理网络接口，规则见 [`OsSysInfo::get_info_physical_only`]
+    pub fn physical_only(mut self, enable: bool) -> Self {
+        self.physical_only = enable;
+        self
+    }
+
+    /// 设置容量显示单位
+    pub fn unit(mut self, unit: MemoryUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// 采集系统信息
+    pub fn build(self) -> OsSysInfo {
+        self.collect(&mut Sources::new())
+    }
+
+    /// 创建按当前配置重复采集的 [`OsSysSampler`]
+    pub fn sampler(self) -> OsSysSampler {
+        OsSysSampler {
+            builder: self,
+            sources: Sources::new(),
+        }
+    }
+
+    /// 刷新选中的数据源并生成快照
+    fn collect(&self, sources: &mut Sources) -> OsSysInfo {
+        let mut sys_info = OsSysInfo {
+            name: System::name().unwrap_or_default(),
+            version: System::os_version().unwrap_or_default(),
+            host: System::host_name().unwrap_or_default(),
+            kernel_version: System::kernel_version().unwrap_or_default(),
+            arch: System::cpu_arch(),
+            unit: self.unit,
+            ..Default::default()
+        };
+        let sys = &mut sources.sys;
+
+        if self.memory {
+            sys.refresh_memory();
+            sys_info.memory_total = sys.total_memory();
+            sys_info.memory_used = sys.used_memory();
+            sys_info.memory_available = sys.available_memory();
+            sys_info.swap_total = sys.total_swap();
+            sys_info.swap_used = sys.used_swap();
+        }
+
+        if self.cpu {
+            sys.refresh_cpu_specifics(CpuRefreshKind::nothing().with_frequency().with_cpu_usage());
+            sys_info.cpu_usage = sources.cpu_primed.then(|| sys.global_cpu_usage());
+            sources.cpu_primed = true;
+            let cpus = sys.cpus();
+            sys_info.cpu = OsCpu {
+                brand: cpus.first().map(|cpu| cpu.brand().trim().to_string()).unwrap_or_default(),
+                physical_core_count: System::physical_core_count().unwrap_or_default(),
+                logical_core_count: cpus.len(),
+                frequency_mhz: cpus.first().map(|cpu| cpu.frequency()).unwrap_or_default(),
+            };
+            sys_info.load_average = load_average();
+        }
+
+        if self.networks {
+            let physical_only = self.physical_only;
+            sources.networks.refresh(true);
+            sys_info.networks = sources.networks
+                .iter()
+                .filter(|(interface_name, data)| {
+                    !physical_only || !is_virtual_interface(interface_name, &data.mac_address().to_string())
+                })
+                .enumerate()
+                .map(|(id, (interface_name, data))| OsNet {
+                    id: id + 1,
+                    name: interface_name.to_string(),
+                    mac: normalize_mac(&data.mac_address().to_string()),
+                    #[cfg(feature = "oui")]
+                    vendor: oui_vendor(&data.mac_address().to_string()),
+                    ipv4: data.ip_networks().iter().filter(|ip| ip.addr.is_ipv4()).map(|ip| ip.to_string()).collect(),
+                    ipv6: data.ip_networks().iter().filter(|ip| ip.addr.is_ipv6()).map(|ip| ip.to_string()).collect(),
+                    total_received: data.total_received(),
+                    total_transmitted: data.total_transmitted(),
+                })
+                .collect();
+        }
+
+        if self.disks {
+            let unit = self.unit;
+            sources.disks.refresh(true);
+            sys_info.disks = sources.disks
+                .iter()
+                .filter(|disk| {
+                    let file_system = disk.file_system().to_string_lossy();
+                    disk.total_space() > 0 && !PSEUDO_FILE_SYSTEMS.contains(&file_system.as_ref())
+                })
+                .map(|disk| OsDisk {
+                    name: disk.name().to_string_lossy().to_string(),
+                    mount_point: disk.mount_point().to_string_lossy().to_string(),
+                    total_space: format_size(disk.total_space(), unit, disk.total_space()),
+                    available_space: format_size(disk.available_space(), unit, disk.total_space()),
+                    total_bytes: disk.total_space(),
+                    available_bytes: disk.available_space(),
+                })
+                .collect();
+        }
+
+        if self.temperatures {
+            // 没有读数的传感器直接跳过
+            sources.components.refresh(true);
+            sys_info.temperatures = sources.components
+                .iter()
+                .filter_map(|component| {
+                    Some(OsTemp {
+                        label: component.label().to_string(),
+                        temperature: component.temperature()?,
+                        critical: component.critical(),
+                    })
+                })
+                .collect();
+        }
+
+        #[cfg(feature = "battery")]
+        if self.battery {
+            sys_info.battery = OsSysInfo::get_battery();
+        }
+
+        sys_info
+    }
+}
+
+/// 采集时使用的sysinfo数据源
+#[derive(Debug)]
+struct Sources {
+    sys: System,
+    networks: Networks,
+    disks: Disks,
+    components: Components,
+    /// CPU计数器是否已经刷新过一次，之后的刷新才能计算使用率
+    cpu_primed: bool,
+}
+
+impl Sources {
+    fn new() -> Self {
+        Self {
+            sys: System::new(),
+            networks: Networks::new(),
+            disks: Disks::new(),
+            components: Components::new(),
+            cpu_primed: false,
+        }
+    }
+}
+
+/// 复用sysinfo数据源的系统信息采样器，适合定时轮询
+///
+/// 与每次调用 [`OsSysInfo::get_info`] 相比，不会重复创建 `System`、`Networks` 等结构，
+/// 只刷新已有数据
+#[derive(Debug)]
+pub struct OsSysSampler {
+    builder: OsSysInfoBuilder,
+    sources: Sources,
+}
+
+impl OsSysSampler {
+    /// 刷新数据并返回新的快照
+    ///
+    /// CPU使用率需要预热：首次刷新只记录计数器，快照中的使用率为None，
+    /// 之后每次刷新给出与上一次刷新之间的平均使用率。两次刷新的间隔应不小于
+    /// `sysinfo::MINIMUM_CPU_UPDATE_INTERVAL`，否则结果不准确
+    pub fn refresh(&mut self) -> OsSysInfo {
+        self.builder.collect(&mut self.sources)
+    }
+
+    /// 下一次 `refresh` 是否能给出CPU使用率，即CPU计数器已预热且采样器包含CPU部分
+    pub fn cpu_ready(&self) -> bool {
+        self.builder.cpu && self.sources.cpu_primed
+    }
+}
+
+/// 快照的保存与读取，用于在支持包等场景中记录系统信息，需要同时启用 `path` 和 `json` 特性
+#[cfg(all(feature = "path", feature = "json"))]
+impl OsSysInfo {
+    /// 将快照以JSON写入文件，必要时创建父目录，`path` 为目录类型时返回错误
+    pub fn save(&self, path: &crate::os_path::PathManager) -> anyhow::Result<()> {
+        path.write_json(self)
+    }
+
+    /// 从 [`OsSysInfo::save`] 写入的JSON文件读取快照
+    ///
+    /// 显示单位不参与序列化，读取后 `memory_display` 按GB显示
+    pub fn load(path: &crate::os_path::PathManager) -> anyhow::Result<OsSysInfo> {
+        path.read_json()
+    }
+}
+
+/// 系统信息的来源
+///
+/// 依赖该trait而不是直接调用 [`OsSysInfo::get_info`] 的代码，可以在测试中注入
+/// [`MockSysInfoProvider`]，不需要真实机器的数据
+pub trait SysInfoProvider {
+    /// 获取系统信息快照
+    fn get_info(&self) -> OsSysInfo;
+}
+
+/// 采集本机实时数据的实现，默认采集所有部分，容量以GB显示
+#[derive(Debug, Clone, Copy)]
+pub struct LiveSysInfoProvider {
+    builder: OsSysInfoBuilder,
+}
+
+impl LiveSysInfoProvider {
+    /// 采集所有部分，与 [`OsSysInfo::get_info`] 相同
+    pub fn new() -> Self {
+        Self::with_builder(OsSysInfoBuilder::all())
+    }
+
+    /// 按builder选择的部分采集
+    pub fn with_builder(builder: OsSysInfoBuilder) -> Self {
+        Self { builder }
+    }
+}
+
+impl Default for LiveSysInfoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SysInfoProvider for LiveSysInfoProvider {
+    fn get_info(&self) -> OsSysInfo {
+        self.builder.build()
+    }
+}
+
+/// 总是返回固定数据的实现，用于测试
+///
+/// 快照的字段不对外公开，可以从JSON反序列化得到需要的数据，未给出的字段取默认值：
+/// `serde_json::from_str::<OsSysInfo>(r#"{"memoryTotal": 1024, "memoryUsed": 512}"#)`
+#[derive(Debug, Clone, Default)]
+pub struct MockSysInfoProvider {
+    info: OsSysInfo,
+}
+
+impl MockSysInfoProvider {
+    /// 每次 `get_info` 都返回 `info` 的副本
+    pub fn new(info: OsSysInfo) -> Self {
+        Self { info }
+    }
+}
+
+impl SysInfoProvider for MockSysInfoProvider {
+    fn get_info(&self) -> OsSysInfo {
+        self.info.clone()
+    }
 }
 
 impl OsSysInfo {
+    /// 创建采集所有部分的采样器，需要选择部分时使用 [`OsSysInfoBuilder::sampler`]
+    pub fn sampler() -> OsSysSampler {
+        OsSysInfoBuilder::all().sampler()
+    }
+
+    /// 获取系统信息快照，容量以GB显示
     pub fn get_info() -> OsSysInfo {
-        let mut sys_info = OsSysInfo::default();
-        let mut sys = System::new_all();
-        sys.refresh_all();
-
-        let total_memory_bytes = sys.total_memory();
-        let used_memory_bytes = sys.used_memory();
-
-        let total_memory_gb = total_memory_bytes as f64 / GB_IN_BYTES;
-        let used_memory_gb = used_memory_bytes as f64 / GB_IN_BYTES;
-        let memory_usage = format!("{:.2} GB / {:.2} GB", used_memory_gb, total_memory_gb);
-
-        sys_info.memory = memory_usage;
-        sys_info.name = System::name().unwrap_or_default();
-        sys_info.version = System::os_version().unwrap_or_default();
-        sys_info.host = System::host_name().unwrap_or_default();
-        sys_info.networks = Networks::new_with_refreshed_list()
+        Self::get_info_with_unit(MemoryUnit::GB)
+    }
+
+    /// 获取系统信息快照，网络列表中不包含回环和虚拟接口
+    ///
+    /// 以下接口会被排除：MAC地址全为0的接口，以及名称以 `lo`、`docker`、`veth`、`br-`、`tun`
+    /// 开头的接口。该规则只是启发式判断，需要完整列表时使用 [`OsSysInfo::get_info`]
+    pub fn get_info_physical_only() -> OsSysInfo {
+        OsSysInfoBuilder::all().physical_only(true).build()
+    }
+
+    /// 获取系统信息快照，内存、交换空间和磁盘容量以指定单位显示
+    pub fn get_info_with_unit(unit: MemoryUnit) -> OsSysInfo {
+        OsSysInfoBuilder::all().unit(unit).build()
+    }
+
+    /// 总内存字节数
+    pub fn memory_total(&self) -> u64 {
+        self.memory_total
+    }
+
+    /// 已用内存字节数
+    pub fn memory_used(&self) -> u64 {
+        self.memory_used
+    }
+
+    /// 可用内存字节数
+    pub fn memory_available(&self) -> u64 {
+        self.memory_available
+    }
+
+    /// 全局CPU使用率（百分比），只有采样器预热后的快照才有值，见 [`OsSysSampler::refresh`]
+    pub fn cpu_usage(&self) -> Option<f32> {
+        self.cpu_usage
+    }
+
+    /// 格式化为 "已用 / 总量"，如 "7.52 GB / 15.50 GB"，单位与采集时指定的单位一致
+    pub fn memory_display(&self) -> String {
+        format_usage(self.memory_used, self.memory_total, self.unit)
+    }
+
+    /// 交换空间总字节数，没有交换空间时为0
+    pub fn swap_total(&self) -> u64 {
+        self.swap_total
+    }
+
+    /// 已用交换空间字节数
+    pub fn swap_used(&self) -> u64 {
+        self.swap_used
+    }
+
+    /// 格式化交换空间为 "已用 / 总量"，没有交换空间时为 "0.00 MB / 0.00 MB"，单位与采集时指定的单位一致
+    pub fn swap_display(&self) -> String {
+        format_usage(self.swap_used, self.swap_total, self.unit)
+    }
+
+    /// 内存使用率（百分比），由字节数直接计算并限制在 [0, 100]，总内存为0时返回0.0
+    pub fn memory_usage_percent(&self) -> f64 {
+        usage_percent(self.memory_used, self.memory_total)
+    }
+
+    /// 所有磁盘合计的使用率（百分比），按总已用字节数除以总容量计算，没有磁盘时返回0.0
+    pub fn disk_usage_percent(&self) -> f64 {
+        let (used, total) = self.disks.iter().fold((0u64, 0u64), |(used, total), disk| {
+            (used + disk.total_bytes.saturating_sub(disk.available_bytes), total + disk.total_bytes)
+        });
+        usage_percent(used, total)
+    }
+
+    /// 最可能用于上网的主网络接口，需要采集时包含网络部分
+    ///
+    /// 优先选择默认路由所在的接口；无法确定时选择拥有非回环地址且累计流量最大的接口，
+    /// 都不满足时返回None
+    pub fn primary_interface(&self) -> Option<OsNet> {
+        pick_primary_interface(&self.networks, default_route_addr())
+    }
+
+    /// 展开为 (指标名, 数值) 列表，便于推送到Prometheus、StatsD等监控系统
+    ///
+    /// 只输出数值字段，名称、版本等文本字段被跳过；未采集的部分不输出。
+    /// 指标名以 `.` 分隔层级，如 `memory.used_bytes`、`network.eth0.received_bytes`、`disk._home.available_bytes`
+    pub fn to_metrics(&self) -> Vec<(String, f64)> {
+        let mut metrics = vec![];
+        let mut push = |key: String, value: f64| metrics.push((key, value));
+
+        if self.memory_total > 0 {
+            push("memory.total_bytes".into(), self.memory_total as f64);
+            push("memory.used_bytes".into(), self.memory_used as f64);
+            push("memory.available_bytes".into(), self.memory_available as f64);
+            push("swap.total_bytes".into(), self.swap_total as f64);
+            push("swap.used_bytes".into(), self.swap_used as f64);
+        }
+        if self.cpu.logical_core_count > 0 {
+            push("cpu.physical_cores".into(), self.cpu.physical_core_count as f64);
+            push("cpu.logical_cores".into(), self.cpu.logical_core_count as f64);
+            push("cpu.frequency_mhz".into(), self.cpu.frequency_mhz as f64);
+        }
+        if let Some(usage) = self.cpu_usage {
+            push("cpu.usage_percent".into(), usage as f64);
+        }
+        if let Some([one, five, fifteen]) = self.load_average {
+            push("load.1m".into(), one);
+            push("load.5m".into(), five);
+            push("load.15m".into(), fifteen);
+        }
+        for disk in &self.disks {
+            let mount = metric_segment(&disk.mount_point);
+            push(format!("disk.{mount}.total_bytes"), disk.total_bytes as f64);
+            push(format!("disk.{mount}.available_bytes"), disk.available_bytes as f64);
+        }
+        for net in &self.networks {
+            let name = metric_segment(&net.name);
+            push(format!("network.{name}.received_bytes"), net.total_received as f64);
+            push(format!("network.{name}.transmitted_bytes"), net.total_transmitted as f64);
+        }
+        for temp in &self.temperatures {
+            push(format!("temperature.{}.celsius", metric_segment(&temp.label)), temp.temperature as f64);
+        }
+        #[cfg(feature = "battery")]
+        if let Some(battery) = &self.battery {
+            push("battery.percentage".into(), battery.percentage as f64);
+        }
+        metrics
+    }
+
+    /// 采样每个CPU核心的使用率（百分比）
+    ///
+    /// 刷新一次CPU数据后等待 `sample` 再刷新一次，取两次之间的使用率。
+    /// `sample` 小于 `sysinfo::MINIMUM_CPU_UPDATE_INTERVAL` 时按最小间隔等待
+    pub fn get_cpu_usage(sample: Duration) -> Vec<f32> {
+        Self::sample_cpu(sample).cpus().iter().map(|cpu| cpu.cpu_usage()).collect()
+    }
+
+    /// 采样全局CPU使用率（百分比），即所有核心的平均值
+    pub fn get_global_cpu_usage(sample: Duration) -> f32 {
+        Self::sample_cpu(sample).global_cpu_usage()
+    }
+
+    /// 采样每个网络接口的吞吐量，返回 (接口名, 每秒接收字节数, 每秒发送字节数)，按接口名排序
+    ///
+    /// 刷新一次网络数据后等待 `sample` 再刷新一次，用两次之间的差值除以实际经过的时间
+    pub fn get_network_throughput(sample: Duration) -> Vec<(String, u64, u64)> {
+        let mut networks = Networks::new_with_refreshed_list();
+        let start = Instant::now();
+        std::thread::sleep(sample);
+        networks.refresh(true);
+        let secs = start.elapsed().as_secs_f64();
+
+        let per_sec = |bytes: u64| match secs > 0.0 {
+            true => (bytes as f64 / secs) as u64,
+            false => 0,
+        };
+        let mut throughput: Vec<_> = networks
+            .iter()
+            .map(|(name, data)| (name.to_string(), per_sec(data.received()), per_sec(data.transmitted())))
+            .collect();
+        throughput.sort_by(|a, b| a.0.cmp(&b.0));
+        throughput
+    }
+
+    /// 获取第一块电池的状态，没有电池或读取失败时返回None
+    ///
+    /// 不包含在 [`OsSysInfo::get_info`] 中，避免在没有电池的机器上额外查询
+    #[cfg(feature = "battery")]
+    pub fn get_battery() -> Option<OsBattery> {
+        use battery::units::ratio::percent;
+        use battery::units::time::second;
+
+        let battery = battery::Manager::new().ok()?.batteries().ok()?.next()?.ok()?;
+        Some(OsBattery {
+            percentage: battery.state_of_charge().get::<percent>(),
+            state: battery.state().to_string(),
+            time_to_full_secs: battery.time_to_full().map(|time| time.get::<second>() as u64),
+        })
+    }
+
+    /// 获取正在运行的进程列表，按 `sort` 排序后最多返回 `limit` 个
+    ///
+    /// 枚举进程开销较大，因此不包含在 [`OsSysInfo::get_info`] 中。
+    /// CPU使用率需要两次刷新才能计算，会阻塞 `sysinfo::MINIMUM_CPU_UPDATE_INTERVAL`
+    pub fn get_processes(sort: ProcessSort, limit: Option<usize>) -> Vec<OsProcess> {
+        let refresh_kind = ProcessRefreshKind::nothing().with_cpu().with_memory();
+        let mut sys = System::new();
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+        std::thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+
+        let mut processes: Vec<OsProcess> = sys
+            .processes()
             .iter()
-            .enumerate()
-            .map(|(id, (interface_name, data))| OsNet {
-                id: id + 1,
-                name: interface_name.to_string(),
-                mac: data.mac_address().to_string(),
+            .map(|(pid, process)| OsProcess {
+                pid: pid.as_u32(),
+                name: process.name().to_string_lossy().to_string(),
+                cpu_usage: process.cpu_usage(),
+                memory_bytes: process.memory(),
             })
             .collect();
+        match sort {
+            ProcessSort::Pid => processes.sort_by_key(|p| p.pid),
+            ProcessSort::Name => processes.sort_by(|a, b| a.name.cmp(&b.name)),
+            ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+            ProcessSort::Memory => processes.sort_by_key(|p| std::cmp::Reverse(p.memory_bytes)),
+        }
+        if let Some(limit) = limit {
+            processes.truncate(limit);
+        }
+        processes
+    }
 
-        sys_info
+    /// 检查每个网络接口能否连接到 `host`，返回 (接口名, 是否可达)，按接口名排序
+    ///
+    /// `host` 格式为 `host:port`，省略端口时使用80。对每个接口，使用其第一个与目标地址族相同的
+    /// 地址（跳过IPv6链路本地地址）作为源地址发起TCP连接，各接口并行检查，每个最多等待 `timeout`。
+    /// 没有可用地址的接口以及域名解析失败时均视为不可达。
+    /// 绑定源地址后实际走哪个出口由系统路由决定，结果只是近似的"该接口是否能上网"
+    pub fn check_connectivity(host: &str, timeout: Duration) -> Vec<(String, bool)> {
+        let target = match host.to_socket_addrs().or_else(|_| (host, 80).to_socket_addrs()) {
+            Ok(mut addrs) => addrs.next(),
+            Err(_) => None,
+        };
+
+        let networks = Networks::new_with_refreshed_list();
+        let mut result: Vec<(String, bool)> = std::thread::scope(|scope| {
+            let checks: Vec<_> = networks
+                .iter()
+                .map(|(name, data)| {
+                    let source = target.and_then(|target| {
+                        data.ip_networks()
+                            .iter()
+                            .map(|ip| ip.addr)
+                            .find(|addr| addr.is_ipv4() == target.is_ipv4() && !is_link_local(addr))
+                            .map(|addr| (addr, target))
+                    });
+                    let check = scope.spawn(move || {
+                        source.is_some_and(|(addr, target)| tcp_reachable(addr, target, timeout))
+                    });
+                    (name.to_string(), check)
+                })
+                .collect();
+            checks
+                .into_iter()
+                .map(|(name, check)| (name, check.join().unwrap_or(false)))
+                .collect()
+        });
+        result.sort_by(|a, b| a.0.cmp(&b.0));
+        result
+    }
+
+    /// 间隔 `sample` 刷新两次CPU数据
+    fn sample_cpu(sample: Duration) -> System {
+        let mut sys = System::new();
+        sys.refresh_cpu_usage();
+        std::thread::sleep(sample.max(MINIMUM_CPU_UPDATE_INTERVAL));
+        sys.refresh_cpu_usage();
+        sys
     }
 }
 
@@ -62,16 +1011,392 @@ mod tests {
         assert!(!info.name.is_empty(), "系统名称不应为空");
         assert!(!info.version.is_empty(), "系统版本不应为空");
         assert!(!info.host.is_empty(), "主机名不应为空");
+        assert!(!info.arch.is_empty(), "CPU架构不应为空");
+        #[cfg(target_os = "linux")]
+        assert!(!info.kernel_version.is_empty(), "Linux下内核版本不应为空");
         
         // 验证内存格式
-        assert!(info.memory.contains("GB"), "内存信息应包含 GB");
-        assert!(info.memory.contains("/"), "内存信息应包含分隔符 /");
+        let memory = info.memory_display();
+        assert!(memory.contains("GB"), "内存信息应包含 GB");
+        assert!(memory.contains("/"), "内存信息应包含分隔符 /");
+        assert!(info.memory_total() > 0, "总内存字节数应大于0");
+        assert!(info.memory_used() > 0, "已用内存字节数应大于0");
+        assert!(info.memory_used() <= info.memory_total(), "已用内存不应超过总内存");
+        assert!(info.memory_available() <= info.memory_total(), "可用内存不应超过总内存");
+
+        // 验证交换空间格式，没有交换空间时为0
+        let swap = info.swap_display();
+        assert!(swap.contains('/'), "交换空间格式错误: {swap}");
+        assert!(info.swap_used() <= info.swap_total(), "已用交换空间不应超过总量");
         
         // 验证网络信息
         for net in info.networks {
             assert!(net.id > 0, "网络接口ID应大于0");
             assert!(!net.name.is_empty(), "网络接口名称不应为空");
             // MAC地址可能为空，所以不做验证
+            for ip in &net.ipv4 {
+                let addr = ip.split('/').next().unwrap();
+                assert!(addr.parse::<std::net::Ipv4Addr>().is_ok(), "应为 IPv4 addr/prefix 格式: {ip}");
+            }
+            for ip in &net.ipv6 {
+                let addr = ip.split('/').next().unwrap();
+                assert!(addr.parse::<std::net::Ipv6Addr>().is_ok(), "应为 IPv6 addr/prefix 格式: {ip}");
+            }
         }
+
+        // 验证CPU信息
+        assert!(info.cpu.logical_core_count > 0, "逻辑核心数应大于0");
+        assert!(info.cpu.physical_core_count <= info.cpu.logical_core_count, "物理核心数不应超过逻辑核心数");
+
+        // 验证磁盘信息
+        for disk in info.disks {
+            assert!(!disk.mount_point.is_empty(), "挂载点不应为空");
+            assert!(disk.total_space.contains("GB"), "磁盘容量应包含 GB");
+            assert!(disk.available_space.contains("GB"), "可用空间应包含 GB");
+        }
+    }
+
+    #[test]
+    fn test_cpu_usage() {
+        let usage = OsSysInfo::get_cpu_usage(Duration::ZERO);
+        assert!(!usage.is_empty(), "至少应有一个CPU核心");
+        for value in usage {
+            assert!((0.0..=100.0).contains(&value), "核心使用率应在0-100之间: {value}");
+        }
+
+        let global = OsSysInfo::get_global_cpu_usage(Duration::ZERO);
+        assert!((0.0..=100.0).contains(&global), "全局使用率应在0-100之间: {global}");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_network_throughput() {
+        let throughput = OsSysInfo::get_network_throughput(Duration::from_millis(100));
+        for (name, _, _) in &throughput {
+            assert!(!name.is_empty(), "网络接口名称不应为空");
+        }
+        assert!(throughput.windows(2).all(|w| w[0].0 <= w[1].0), "结果应按接口名排序");
+    }
+
+    #[test]
+    fn test_memory_unit() {
+        const MB: u64 = 1_048_576;
+        assert_eq!(format_size(512 * MB, MemoryUnit::MB, 0), "512.00 MB");
+        assert_eq!(format_size(512 * MB, MemoryUnit::GB, 0), "0.50 GB");
+        assert_eq!(format_size(512 * MB, MemoryUnit::Auto, 512 * MB), "512.00 MB");
+        assert_eq!(format_size(2048 * MB, MemoryUnit::Auto, 2048 * MB), "2.00 GB");
+        assert_eq!(format_usage(512 * MB, 2048 * MB, MemoryUnit::Auto), "0.50 GB / 2.00 GB");
+        assert_eq!(format_usage(0, 0, MemoryUnit::Auto), "0.00 MB / 0.00 MB");
+
+        let info = OsSysInfo::get_info_with_unit(MemoryUnit::MB);
+        assert!(info.memory_display().ends_with("MB"), "内存信息应以MB显示: {}", info.memory_display());
+    }
+
+    #[test]
+    fn test_builder_sections() {
+        let info = OsSysInfoBuilder::new().memory(true).build();
+        assert!(!info.host.is_empty(), "主机名总是会填充");
+        assert!(info.memory_total > 0, "选中的内存部分应被填充");
+        assert!(info.networks.is_empty(), "未选中的网络部分应为空");
+        assert!(info.disks.is_empty(), "未选中的磁盘部分应为空");
+        assert_eq!(info.cpu.logical_core_count, 0, "未选中的CPU部分应为默认值");
+        assert!(info.temperatures.is_empty(), "未选中的温度部分应为空");
+
+        let info = OsSysInfoBuilder::new().cpu(true).build();
+        assert_eq!(info.memory_total, 0);
+        assert!(info.cpu.logical_core_count > 0);
+    }
+
+    #[test]
+    fn test_virtual_interface_filter() {
+        assert!(is_virtual_interface("lo", "00:00:00:00:00:00"));
+        assert!(is_virtual_interface("docker0", "02:42:ac:11:00:02"));
+        assert!(is_virtual_interface("veth1a2b3c", "ae:12:34:56:78:9a"));
+        assert!(is_virtual_interface("br-5f3e", "02:42:0a:00:00:01"));
+        assert!(is_virtual_interface("tun0", "00:00:00:00:00:00"));
+        assert!(!is_virtual_interface("eth0", "52:54:00:12:34:56"));
+
+        let info = OsSysInfo::get_info_physical_only();
+        for net in &info.networks {
+            assert!(!is_virtual_interface(&net.name, &net.mac), "不应包含虚拟接口: {}", net.name);
+        }
+    }
+
+    #[test]
+    fn test_temperatures() {
+        // 容器或虚拟机中可能没有传感器，此时应为空而不是出错
+        let info = OsSysInfoBuilder::new().temperatures(true).build();
+        for temp in &info.temperatures {
+            assert!(!temp.label.is_empty());
+            assert!(temp.temperature.is_finite());
+        }
+    }
+
+    #[test]
+    fn test_camel_case_serialization() {
+        let info = OsSysInfoBuilder::new().memory(true).networks(true).build();
+        let value = serde_json::to_value(&info).unwrap();
+        assert!(value.get("memoryTotal").is_some());
+        assert!(value.get("memory_total").is_none());
+        assert!(value.get("unit").is_none(), "显示单位不应序列化");
+        if let Some(net) = value["networks"].as_array().and_then(|nets| nets.first()) {
+            assert!(net.get("ipv4").is_some());
+            assert!(net.get("ipv6").is_some());
+        }
+    }
+
+    #[test]
+    fn test_get_processes() {
+        let all = OsSysInfo::get_processes(ProcessSort::Pid, None);
+        assert!(!all.is_empty(), "至少应包含当前测试进程");
+        assert!(all.iter().any(|p| p.pid() == std::process::id() && !p.name().is_empty()));
+        assert!(all.windows(2).all(|w| w[0].pid() <= w[1].pid()));
+
+        let top = OsSysInfo::get_processes(ProcessSort::Memory, Some(3));
+        assert!(top.len() <= 3);
+        assert!(top.windows(2).all(|w| w[0].memory_bytes() >= w[1].memory_bytes()));
+        assert!(top.iter().all(|p| p.cpu_usage() >= 0.0));
+    }
+
+    #[cfg(feature = "battery")]
+    #[test]
+    fn test_battery() {
+        assert!(OsSysInfo::get_info().battery.is_none(), "默认不采集电池状态");
+
+        // 台式机或容器中没有电池，此时为None
+        if let Some(battery) = OsSysInfoBuilder::new().battery(true).build().battery {
+            assert!((0.0..=100.0).contains(&battery.percentage));
+            assert!(!battery.state.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_load_average() {
+        let info = OsSysInfoBuilder::new().cpu(true).build();
+        if cfg!(windows) {
+            assert!(info.load_average.is_none(), "Windows不支持平均负载");
+        } else {
+            let load = info.load_average.expect("Unix下应有平均负载");
+            assert!(load.iter().all(|value| *value >= 0.0));
+        }
+        assert!(OsSysInfoBuilder::new().build().load_average.is_none(), "未选中CPU部分时不采集");
+    }
+
+    #[test]
+    fn test_memory_usage_percent() {
+        let mut info = OsSysInfo::default();
+        assert_eq!(info.memory_usage_percent(), 0.0, "总内存为0时应返回0");
+
+        info.memory_total = 8 * 1024;
+        info.memory_used = 2 * 1024;
+        assert_eq!(info.memory_usage_percent(), 25.0);
+
+        info.memory_used = 16 * 1024;
+        assert_eq!(info.memory_usage_percent(), 100.0, "应限制在100以内");
+
+        let percent = OsSysInfoBuilder::new().memory(true).build().memory_usage_percent();
+        assert!(percent > 0.0 && percent <= 100.0);
+    }
+
+    #[test]
+    fn test_check_connectivity() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = listener.local_addr().unwrap().to_string();
+
+        let loopback: Vec<String> = Networks::new_with_refreshed_list()
+            .iter()
+            .filter(|(_, data)| data.ip_networks().iter().any(|ip| ip.addr.is_loopback()))
+            .map(|(name, _)| name.to_string())
+            .collect();
+        let result = OsSysInfo::check_connectivity(&host, Duration::from_secs(2));
+        for name in &loopback {
+            assert!(result.iter().any(|(n, ok)| n == name && *ok), "回环接口应能连接本地监听端口");
+        }
+
+        let unresolved = OsSysInfo::check_connectivity("invalid.host.ostring-base.test", Duration::from_millis(200));
+        assert!(unresolved.iter().all(|(_, ok)| !ok), "无法解析的主机应视为不可达");
+    }
+
+    #[test]
+    fn test_sampler_refresh() {
+        let mut sampler = OsSysInfo::sampler();
+        let first = sampler.refresh();
+        let second = sampler.refresh();
+        assert!(first.memory_total > 0);
+        assert_eq!(first.memory_total, second.memory_total, "总内存在两次采样之间不应变化");
+        assert_eq!(first.networks.len(), second.networks.len());
+        assert_eq!(first.disks.len(), second.disks.len());
+        assert_eq!(first.disks.len(), OsSysInfo::get_info().disks.len());
+
+        let mut memory_only = OsSysInfoBuilder::new().memory(true).sampler();
+        assert!(memory_only.refresh().networks.is_empty());
+    }
+
+    #[test]
+    fn test_primary_interface() {
+        let net = |name: &str, ipv4: &[&str], traffic: u64| OsNet {
+            name: name.to_string(),
+            ipv4: ipv4.iter().map(|ip| ip.to_string()).collect(),
+            total_received: traffic,
+            ..Default::default()
+        };
+        let networks = vec![
+            net("lo", &["127.0.0.1/8"], 1_000_000),
+            net("eth0", &["192.168.1.10/24"], 100),
+            net("wlan0", &["10.0.0.5/24"], 5_000),
+            net("down0", &[], 9_000_000),
+        ];
+
+        let routed = pick_primary_interface(&networks, Some("192.168.1.10".parse().unwrap())).unwrap();
+        assert_eq!(routed.name(), "eth0", "应优先选择默认路由所在的接口");
+        assert_eq!(routed.ipv4(), ["192.168.1.10/24"]);
+        assert!(routed.ipv6().is_empty());
+        assert_eq!(routed.total_received(), 100);
+        let busiest = pick_primary_interface(&networks, None);
+        assert_eq!(busiest.unwrap().name, "wlan0", "应选择非回环接口中流量最大的");
+        assert!(pick_primary_interface(&networks[..1], None).is_none(), "只有回环接口时应返回None");
+
+        let info = OsSysInfoBuilder::new().networks(true).build();
+        if let Some(primary) = info.primary_interface() {
+            assert!(info.networks.iter().any(|net| net.name == primary.name));
+        }
+    }
+
+    #[test]
+    fn test_to_metrics() {
+        let info = OsSysInfo {
+            memory_total: 8,
+            memory_used: 3,
+            load_average: Some([0.5, 0.25, 0.125]),
+            disks: vec![
+                OsDisk { mount_point: "/".to_string(), total_bytes: 100, available_bytes: 40, ..Default::default() },
+                OsDisk { mount_point: "C:\\".to_string(), total_bytes: 200, available_bytes: 50, ..Default::default() },
+            ],
+            networks: vec![OsNet {
+                name: "Wi-Fi 2".to_string(),
+                total_received: 10,
+                total_transmitted: 20,
+                ..Default::default()
+            }],
+            temperatures: vec![OsTemp { label: "cpu.core 0".to_string(), temperature: 42.0, critical: None }],
+            ..Default::default()
+        };
+        let metrics = info.to_metrics();
+        let get = |key: &str| metrics.iter().find(|(k, _)| k == key).map(|(_, v)| *v);
+
+        assert_eq!(get("memory.total_bytes"), Some(8.0));
+        assert_eq!(get("memory.used_bytes"), Some(3.0));
+        assert_eq!(get("load.15m"), Some(0.125));
+        assert_eq!(get("disk._.total_bytes"), Some(100.0));
+        assert_eq!(get("disk._.available_bytes"), Some(40.0));
+        assert_eq!(get("disk.C__.total_bytes"), Some(200.0));
+        assert_eq!(get("disk.C__.available_bytes"), Some(50.0));
+        assert_eq!(get("network.Wi-Fi_2.received_bytes"), Some(10.0));
+        assert_eq!(get("network.Wi-Fi_2.transmitted_bytes"), Some(20.0));
+        assert_eq!(get("temperature.cpu_core_0.celsius"), Some(42.0));
+        assert_eq!(get("cpu.logical_cores"), None, "未采集的CPU部分不应输出");
+
+        let live = OsSysInfo::get_info().to_metrics();
+        assert!(live.iter().all(|(_, value)| value.is_finite()));
+    }
+
+    #[test]
+    fn test_sys_info_provider() {
+        fn memory_alert(provider: &dyn SysInfoProvider) -> bool {
+            provider.get_info().memory_usage_percent() > 90.0
+        }
+
+        let busy: OsSysInfo = serde_json::from_str(r#"{"memoryTotal": 100, "memoryUsed": 95}"#).unwrap();
+        assert!(memory_alert(&MockSysInfoProvider::new(busy)));
+        let partial: OsSysInfo = serde_json::from_str(r#"{"cpu": {"logicalCoreCount": 4}}"#).unwrap();
+        assert_eq!(partial.cpu.logical_core_count, 4, "子结构中未给出的字段也应取默认值");
+        assert!(!memory_alert(&MockSysInfoProvider::default()), "默认快照的内存为0");
+
+        let live = LiveSysInfoProvider::with_builder(OsSysInfoBuilder::new().memory(true)).get_info();
+        assert!(live.memory_total() > 0);
+        assert!(live.networks.is_empty(), "应只采集builder选择的部分");
+    }
+
+    #[test]
+    fn test_disk_usage_percent() {
+        let disk = |total_bytes, available_bytes| OsDisk { total_bytes, available_bytes, ..Default::default() };
+        assert_eq!(disk(200, 50).usage_percent(), 75.0);
+        assert_eq!(disk(0, 0).usage_percent(), 0.0, "总容量为0时应返回0");
+
+        let info = OsSysInfo { disks: vec![disk(100, 100), disk(300, 100), disk(0, 0)], ..Default::default() };
+        assert_eq!(info.disk_usage_percent(), 50.0, "应按字节数合计计算");
+        assert_eq!(OsSysInfo::default().disk_usage_percent(), 0.0);
+
+        let live = OsSysInfoBuilder::new().disks(true).build();
+        for disk in &live.disks {
+            assert!((0.0..=100.0).contains(&disk.usage_percent()));
+        }
+        assert!((0.0..=100.0).contains(&live.disk_usage_percent()));
+    }
+
+    #[cfg(all(feature = "path", feature = "json"))]
+    #[test]
+    fn test_save_and_load() {
+        use crate::os_path::PathManager;
+
+        let dir = std::env::temp_dir().join("ostring_base_test").join("sysinfo_save_and_load");
+        let _ = std::fs::remove_dir_all(&dir);
+        let file = PathManager::dir(&dir).join_file("sysinfo.json").unwrap();
+
+        let info = OsSysInfo::get_info();
+        info.save(&file).unwrap();
+        let loaded = OsSysInfo::load(&file).unwrap();
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&info).unwrap(),
+            "读取的快照应与保存前一致"
+        );
+        assert_eq!(loaded.memory_total(), info.memory_total());
+        assert_eq!(loaded.networks.len(), info.networks.len());
+        assert_eq!(loaded.memory_display(), info.memory_display());
+
+        assert!(info.save(&PathManager::dir(&dir)).is_err(), "目录不能保存快照");
+    }
+
+    #[test]
+    fn test_sampler_cpu_warm_up() {
+        let mut sampler = OsSysInfoBuilder::new().cpu(true).sampler();
+        assert!(!sampler.cpu_ready());
+        let first = sampler.refresh();
+        assert!(first.cpu_usage().is_none(), "首次刷新不应给出CPU使用率");
+        assert!(first.cpu.logical_core_count > 0);
+        assert!(sampler.cpu_ready());
+
+        std::thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+        let usage = sampler.refresh().cpu_usage().expect("预热后应给出CPU使用率");
+        assert!((0.0..=100.0).contains(&usage), "使用率应在0-100之间: {usage}");
+
+        assert!(OsSysInfo::get_info().cpu_usage().is_none(), "一次性采集无法计算使用率");
+        let mut memory_only = OsSysInfoBuilder::new().memory(true).sampler();
+        memory_only.refresh();
+        assert!(!memory_only.cpu_ready(), "未包含CPU部分时不应就绪");
+    }
+
+    #[test]
+    fn test_normalize_mac() {
+        assert_eq!(normalize_mac("52:54:00:ab:cd:ef"), "52:54:00:AB:CD:EF");
+        assert_eq!(normalize_mac("52-54-00-AB-CD-EF"), "52:54:00:AB:CD:EF");
+        assert_eq!(normalize_mac("5254.00ab.cdef"), "52:54:00:AB:CD:EF");
+        assert_eq!(normalize_mac("525400abcdef"), "52:54:00:AB:CD:EF");
+        assert_eq!(normalize_mac("not-a-mac"), "NOT-A-MAC", "无法解析时只转为大写");
+
+        let info = OsSysInfoBuilder::new().networks(true).build();
+        for net in &info.networks {
+            assert_eq!(net.mac, normalize_mac(&net.mac), "采集到的MAC应已规范化: {}", net.mac);
+        }
+    }
+
+    #[cfg(feature = "oui")]
+    #[test]
+    fn test_oui_vendor() {
+        assert!(OUI_VENDORS.windows(2).all(|pair| pair[0].0 < pair[1].0), "前缀表应按前缀排序");
+        assert_eq!(oui_vendor("08:00:27:12:34:56").as_deref(), Some("Oracle VirtualBox"));
+        assert_eq!(oui_vendor("00-50-56-c0-00-01").as_deref(), Some("VMware"));
+        assert_eq!(oui_vendor("00:00:00:00:00:00"), None);
+        assert_eq!(oui_vendor(""), None);
+    }
+}
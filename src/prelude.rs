@@ -0,0 +1,8 @@
+#[cfg(feature = "autolaunch")]
+pub use crate::os_autolaunch::AutoLaunchManager;
+#[cfg(feature = "path")]
+pub use crate::os_path::{PathManager, PathType};
+#[cfg(feature = "serialport")]
+pub use crate::os_serialport::{serial_port_list, PortInfo};
+#[cfg(feature = "sysinfo")]
+pub use crate::os_sysinfo::{OsSysInfo, SysInfoProvider};